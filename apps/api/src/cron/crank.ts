@@ -1,4 +1,4 @@
-import { and, eq, lt, isNotNull, sql } from 'drizzle-orm'
+import { and, eq, lt, inArray, isNotNull, sql } from 'drizzle-orm'
 import { claims, quests, proofs, users } from '../db/schema'
 import { createDbClient } from '../db/client'
 import { OracleService } from '../services/oracle.service'
@@ -95,4 +95,27 @@ export async function runCrank(env: Env): Promise<void> {
       console.error(`Failed to auto-approve claim ${claim.id}:`, err)
     }
   }
+
+  // 3. Expire quests past their time limit; live claims keep running on-chain
+  const expiredQuests = await db.select()
+    .from(quests)
+    .where(and(
+      inArray(quests.status, ['active', 'claimed']),
+      isNotNull(quests.deadline),
+      lt(quests.deadline, now),
+    ))
+    .all()
+
+  for (const quest of expiredQuests) {
+    try {
+      const oracle = new OracleService(env)
+      await oracle.expireQuest(quest)
+
+      await db.update(quests).set({ status: 'expired' }).where(eq(quests.id, quest.id))
+
+      console.log(`Expired quest ${quest.id}`)
+    } catch (err) {
+      console.error(`Failed to expire quest ${quest.id}:`, err)
+    }
+  }
 }
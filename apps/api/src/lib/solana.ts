@@ -1,5 +1,5 @@
 import { Connection, PublicKey, Keypair } from '@solana/web3.js'
-import { getAssociatedTokenAddressSync } from '@solana/spl-token'
+import { getAssociatedTokenAddressSync, NATIVE_MINT } from '@solana/spl-token'
 import type { Env } from '../types'
 
 export function getConnection(env: Env): Connection {
@@ -21,10 +21,21 @@ export function deriveConfigPda(programId: PublicKey): [PublicKey, number] {
   return PublicKey.findProgramAddressSync([Buffer.from('config')], programId)
 }
 
-export function deriveQuestPda(programId: PublicKey, questId: bigint): [PublicKey, number] {
+export function deriveOracleSetPda(programId: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([Buffer.from('oracles')], programId)
+}
+
+export function deriveQuestPda(
+  programId: PublicKey,
+  creator: PublicKey,
+  creatorNonce: bigint,
+): [PublicKey, number] {
   const buf = Buffer.alloc(8)
-  buf.writeBigUInt64LE(questId)
-  return PublicKey.findProgramAddressSync([Buffer.from('quest'), buf], programId)
+  buf.writeBigUInt64LE(creatorNonce)
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('quest'), creator.toBuffer(), buf],
+    programId,
+  )
 }
 
 export function deriveEscrowPda(programId: PublicKey, questPda: PublicKey): [PublicKey, number] {
@@ -34,6 +45,13 @@ export function deriveEscrowPda(programId: PublicKey, questPda: PublicKey): [Pub
   )
 }
 
+export function deriveUnwrapPda(programId: PublicKey, questPda: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('unwrap'), questPda.toBuffer()],
+    programId,
+  )
+}
+
 export function deriveClaimPda(
   programId: PublicKey,
   questPda: PublicKey,
@@ -45,6 +63,29 @@ export function deriveClaimPda(
   )
 }
 
+export function deriveProfilePda(programId: PublicKey, wallet: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('profile'), wallet.toBuffer()],
+    programId,
+  )
+}
+
+export function deriveStatsPda(programId: PublicKey, wallet: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('stats'), wallet.toBuffer()],
+    programId,
+  )
+}
+
 export function getAta(mint: PublicKey, owner: PublicKey): PublicKey {
   return getAssociatedTokenAddressSync(mint, owner)
 }
+
+// Native SOL payouts go straight to the wallet; Anchor reads the program id as `None`
+export function getPayoutTokenAccount(
+  programId: PublicKey,
+  mint: PublicKey,
+  owner: PublicKey,
+): PublicKey {
+  return mint.equals(NATIVE_MINT) ? programId : getAta(mint, owner)
+}
@@ -3,7 +3,7 @@ import { claims, quests, users } from '../db/schema'
 import type { Database } from '../db/client'
 import { nanoid } from 'nanoid'
 import { PublicKey } from '@solana/web3.js'
-import { deriveQuestPda, deriveClaimPda } from '../lib/solana'
+import { deriveClaimPda } from '../lib/solana'
 
 const PROOF_DEADLINE_HOURS = 24
 const REVIEW_DEADLINE_HOURS = 24
@@ -78,7 +78,7 @@ export async function createClaim(
 
   // Derive claim PDA
   const pid = new PublicKey(programId)
-  const [questPda] = deriveQuestPda(pid, BigInt(quest.onchainId))
+  const questPda = new PublicKey(quest.questPda)
   const [claimPda] = deriveClaimPda(pid, questPda, new PublicKey(claimerPubkey))
 
   const claim = {
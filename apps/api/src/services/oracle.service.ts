@@ -1,18 +1,31 @@
-import { Connection, Keypair, PublicKey, Transaction, sendAndConfirmTransaction } from '@solana/web3.js'
+import {
+  Connection,
+  Ed25519Program,
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  Transaction,
+  sendAndConfirmTransaction,
+} from '@solana/web3.js'
 import { getAssociatedTokenAddressSync, TOKEN_PROGRAM_ID } from '@solana/spl-token'
 import {
   getConnection,
   getOracleKeypair,
   getProgramId,
   deriveConfigPda,
-  deriveQuestPda,
+  deriveOracleSetPda,
   deriveEscrowPda,
+  deriveUnwrapPda,
   deriveClaimPda,
-  getAta,
+  deriveProfilePda,
+  deriveStatsPda,
+  getPayoutTokenAccount,
 } from '../lib/solana'
 import type { Env } from '../types'
 import type { quests, claims } from '../db/schema'
 
+const ATTESTATION_DOMAIN = Buffer.from('quest-attestation-v1')
+
 type Quest = typeof quests.$inferSelect
 type Claim = typeof claims.$inferSelect
 
@@ -28,29 +41,58 @@ export class OracleService {
   }
 
   async approveCompletion(quest: Quest, claim: Claim): Promise<string> {
+    return this.sendApproval('approve_completion', quest, claim)
+  }
+
+  // Partial credit: the claimer gets `bps` of the reward, the creator the rest
+  async settlePartial(quest: Quest, claim: Claim, bps: number): Promise<string> {
+    return this.sendApproval('settle_partial', quest, claim, { bps })
+  }
+
+  private async sendApproval(
+    method: 'approve_completion' | 'settle_partial',
+    quest: Quest,
+    claim: Claim,
+    args?: Record<string, any>,
+  ): Promise<string> {
     const [configPda] = deriveConfigPda(this.programId)
-    const [questPda] = deriveQuestPda(this.programId, BigInt(quest.onchainId))
+    const questPda = new PublicKey(quest.questPda)
     const [escrowPda] = deriveEscrowPda(this.programId, questPda)
     const claimerPubkey = await this.resolveClaimerPubkey(claim)
     const [claimPda] = deriveClaimPda(this.programId, questPda, claimerPubkey)
 
     const rewardMint = new PublicKey(quest.rewardMint)
-    const claimerAta = getAta(rewardMint, claimerPubkey)
-    const treasuryPubkey = new PublicKey(this.env.TREASURY_TOKEN_ACCOUNT)
+    const creatorPubkey = new PublicKey(quest.creatorId) // Will need to resolve from users table
+    const claimerAta = getPayoutTokenAccount(this.programId, rewardMint, claimerPubkey)
+    const creatorAta = getPayoutTokenAccount(this.programId, rewardMint, creatorPubkey)
+    const treasuryPubkey = getAssociatedTokenAddressSync(
+      rewardMint,
+      new PublicKey(this.env.TREASURY_WALLET),
+    )
 
-    // Build the approve_completion instruction
+    // Both instructions share the approve_completion accounts
     // Using raw transaction construction since we don't have the Anchor IDL loaded as a Program
     // In production, this would use the Anchor Program instance
-    const ix = await this.buildInstruction('approve_completion', {
+    const ix = await this.buildInstruction(method, {
       config: configPda,
+      oracleSet: deriveOracleSetPda(this.programId)[0],
       quest: questPda,
       claim: claimPda,
+      creatorStats: deriveStatsPda(this.programId, creatorPubkey)[0],
+      claimerStats: deriveStatsPda(this.programId, claimerPubkey)[0],
+      profile: deriveProfilePda(this.programId, claimerPubkey)[0],
       escrow: escrowPda,
+      rewardMint,
+      creator: creatorPubkey,
+      creatorTokenAccount: creatorAta,
+      claimer: claimerPubkey,
       claimerTokenAccount: claimerAta,
       treasury: treasuryPubkey,
-      authority: this.keypair.publicKey,
+      unwrapAccount: deriveUnwrapPda(this.programId, questPda)[0],
+      oracle: this.keypair.publicKey,
       tokenProgram: TOKEN_PROGRAM_ID,
-    })
+      systemProgram: SystemProgram.programId,
+    }, args)
 
     const tx = new Transaction().add(ix)
     tx.feePayer = this.keypair.publicKey
@@ -62,26 +104,85 @@ export class OracleService {
 
   async rejectCompletion(quest: Quest, claim: Claim, safetyFlagged: boolean): Promise<string> {
     const [configPda] = deriveConfigPda(this.programId)
-    const [questPda] = deriveQuestPda(this.programId, BigInt(quest.onchainId))
+    const questPda = new PublicKey(quest.questPda)
+    const claimerPubkey = await this.resolveClaimerPubkey(claim)
+    const [claimPda] = deriveClaimPda(this.programId, questPda, claimerPubkey)
+
+    // Funds stay in escrow until finalize_rejection runs after the dispute window
+    const ix = await this.buildInstruction('reject_completion', {
+      config: configPda,
+      oracleSet: deriveOracleSetPda(this.programId)[0],
+      quest: questPda,
+      claim: claimPda,
+      oracle: this.keypair.publicKey,
+    }, { safetyFlagged })
+
+    const tx = new Transaction().add(ix)
+    tx.feePayer = this.keypair.publicKey
+    tx.recentBlockhash = (await this.connection.getLatestBlockhash()).blockhash
+
+    const sig = await sendAndConfirmTransaction(this.connection, tx, [this.keypair])
+    return sig
+  }
+
+  async requestRevision(quest: Quest, claim: Claim): Promise<string> {
+    const questPda = new PublicKey(quest.questPda)
+    const claimerPubkey = await this.resolveClaimerPubkey(claim)
+    const [claimPda] = deriveClaimPda(this.programId, questPda, claimerPubkey)
+
+    // Sends the proof back to the claimer with a fresh proof window
+    const ix = await this.buildInstruction('request_revision', {
+      config: deriveConfigPda(this.programId)[0],
+      oracleSet: deriveOracleSetPda(this.programId)[0],
+      quest: questPda,
+      claim: claimPda,
+      profile: deriveProfilePda(this.programId, claimerPubkey)[0],
+      reviewer: this.keypair.publicKey,
+    })
+
+    const tx = new Transaction().add(ix)
+    tx.feePayer = this.keypair.publicKey
+    tx.recentBlockhash = (await this.connection.getLatestBlockhash()).blockhash
+
+    const sig = await sendAndConfirmTransaction(this.connection, tx, [this.keypair])
+    return sig
+  }
+
+  async finalizeRejection(quest: Quest, claim: Claim): Promise<string> {
+    const questPda = new PublicKey(quest.questPda)
     const [escrowPda] = deriveEscrowPda(this.programId, questPda)
     const claimerPubkey = await this.resolveClaimerPubkey(claim)
     const [claimPda] = deriveClaimPda(this.programId, questPda, claimerPubkey)
 
     const rewardMint = new PublicKey(quest.rewardMint)
     const creatorPubkey = new PublicKey(quest.creatorId) // Will need to resolve from users table
-    const claimerAta = getAta(rewardMint, claimerPubkey)
-    const creatorAta = getAta(rewardMint, creatorPubkey)
+    const claimerAta = getPayoutTokenAccount(this.programId, rewardMint, claimerPubkey)
+    const creatorAta = getPayoutTokenAccount(this.programId, rewardMint, creatorPubkey)
 
-    const ix = await this.buildInstruction('reject_completion', {
+    const [configPda] = deriveConfigPda(this.programId)
+    const treasuryPubkey = getAssociatedTokenAddressSync(
+      rewardMint,
+      new PublicKey(this.env.TREASURY_WALLET),
+    )
+
+    const ix = await this.buildInstruction('finalize_rejection', {
       config: configPda,
       quest: questPda,
       claim: claimPda,
+      profile: deriveProfilePda(this.programId, claimerPubkey)[0],
+      claimerStats: deriveStatsPda(this.programId, claimerPubkey)[0],
       escrow: escrowPda,
+      rewardMint,
+      creator: creatorPubkey,
       creatorTokenAccount: creatorAta,
+      claimer: claimerPubkey,
       claimerTokenAccount: claimerAta,
-      authority: this.keypair.publicKey,
+      treasury: treasuryPubkey,
+      unwrapAccount: deriveUnwrapPda(this.programId, questPda)[0],
+      cranker: this.keypair.publicKey,
       tokenProgram: TOKEN_PROGRAM_ID,
-    }, { safetyFlagged })
+      systemProgram: SystemProgram.programId,
+    })
 
     const tx = new Transaction().add(ix)
     tx.feePayer = this.keypair.publicKey
@@ -92,22 +193,64 @@ export class OracleService {
   }
 
   async expireClaim(quest: Quest, claim: Claim): Promise<string> {
-    const [questPda] = deriveQuestPda(this.programId, BigInt(quest.onchainId))
+    const questPda = new PublicKey(quest.questPda)
     const [escrowPda] = deriveEscrowPda(this.programId, questPda)
     const claimerPubkey = await this.resolveClaimerPubkey(claim)
     const [claimPda] = deriveClaimPda(this.programId, questPda, claimerPubkey)
 
     const rewardMint = new PublicKey(quest.rewardMint)
     const creatorPubkey = new PublicKey(quest.creatorId)
-    const creatorAta = getAta(rewardMint, creatorPubkey)
+    const creatorAta = getPayoutTokenAccount(this.programId, rewardMint, creatorPubkey)
+
+    const [configPda] = deriveConfigPda(this.programId)
+    const treasuryPubkey = getAssociatedTokenAddressSync(
+      rewardMint,
+      new PublicKey(this.env.TREASURY_WALLET),
+    )
 
     const ix = await this.buildInstruction('expire_claim', {
+      config: configPda,
       quest: questPda,
       claim: claimPda,
+      profile: deriveProfilePda(this.programId, claimerPubkey)[0],
+      claimerStats: deriveStatsPda(this.programId, claimerPubkey)[0],
+      escrow: escrowPda,
+      rewardMint,
+      creator: creatorPubkey,
+      creatorTokenAccount: creatorAta,
+      treasury: treasuryPubkey,
+      unwrapAccount: deriveUnwrapPda(this.programId, questPda)[0],
+      cranker: this.keypair.publicKey,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    })
+
+    const tx = new Transaction().add(ix)
+    tx.feePayer = this.keypair.publicKey
+    tx.recentBlockhash = (await this.connection.getLatestBlockhash()).blockhash
+
+    const sig = await sendAndConfirmTransaction(this.connection, tx, [this.keypair])
+    return sig
+  }
+
+  async expireQuest(quest: Quest): Promise<string> {
+    const questPda = new PublicKey(quest.questPda)
+    const [escrowPda] = deriveEscrowPda(this.programId, questPda)
+
+    const rewardMint = new PublicKey(quest.rewardMint)
+    const creatorPubkey = new PublicKey(quest.creatorId)
+    const creatorAta = getPayoutTokenAccount(this.programId, rewardMint, creatorPubkey)
+
+    const ix = await this.buildInstruction('expire_quest', {
+      quest: questPda,
       escrow: escrowPda,
+      rewardMint,
+      creator: creatorPubkey,
       creatorTokenAccount: creatorAta,
+      unwrapAccount: deriveUnwrapPda(this.programId, questPda)[0],
       cranker: this.keypair.publicKey,
       tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
     })
 
     const tx = new Transaction().add(ix)
@@ -120,24 +263,38 @@ export class OracleService {
 
   async autoApprove(quest: Quest, claim: Claim): Promise<string> {
     const [configPda] = deriveConfigPda(this.programId)
-    const [questPda] = deriveQuestPda(this.programId, BigInt(quest.onchainId))
+    const questPda = new PublicKey(quest.questPda)
     const [escrowPda] = deriveEscrowPda(this.programId, questPda)
     const claimerPubkey = await this.resolveClaimerPubkey(claim)
     const [claimPda] = deriveClaimPda(this.programId, questPda, claimerPubkey)
 
     const rewardMint = new PublicKey(quest.rewardMint)
-    const claimerAta = getAta(rewardMint, claimerPubkey)
-    const treasuryPubkey = new PublicKey(this.env.TREASURY_TOKEN_ACCOUNT)
+    const creatorPubkey = new PublicKey(quest.creatorId) // Will need to resolve from users table
+    const claimerAta = getPayoutTokenAccount(this.programId, rewardMint, claimerPubkey)
+    const creatorAta = getPayoutTokenAccount(this.programId, rewardMint, creatorPubkey)
+    const treasuryPubkey = getAssociatedTokenAddressSync(
+      rewardMint,
+      new PublicKey(this.env.TREASURY_WALLET),
+    )
 
     const ix = await this.buildInstruction('auto_approve', {
       config: configPda,
       quest: questPda,
       claim: claimPda,
+      creatorStats: deriveStatsPda(this.programId, creatorPubkey)[0],
+      claimerStats: deriveStatsPda(this.programId, claimerPubkey)[0],
+      profile: deriveProfilePda(this.programId, claimerPubkey)[0],
       escrow: escrowPda,
+      rewardMint,
+      creator: creatorPubkey,
+      creatorTokenAccount: creatorAta,
+      claimer: claimerPubkey,
       claimerTokenAccount: claimerAta,
       treasury: treasuryPubkey,
+      unwrapAccount: deriveUnwrapPda(this.programId, questPda)[0],
       cranker: this.keypair.publicKey,
       tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
     })
 
     const tx = new Transaction().add(ix)
@@ -148,6 +305,46 @@ export class OracleService {
     return sig
   }
 
+  // Signs a verdict that the claimer (or any relayer) submits with
+  // approve_with_attestation / reject_with_attestation, so the oracle never
+  // needs to hold SOL. The returned instruction must directly precede the settle ix.
+  async signAttestation(
+    quest: Quest,
+    claim: Claim,
+    // the 32-byte proof hash the verdict was reached on, as stored on the claim
+    proofHash: Buffer,
+    approve: boolean,
+    confidence: number,
+    safetyFlags: number,
+    // the claim's on-chain attestation_nonce plus one; it counts accepted attestations
+    nonce: bigint,
+  ) {
+    const questPda = new PublicKey(quest.questPda)
+    const claimerPubkey = await this.resolveClaimerPubkey(claim)
+    const [claimPda] = deriveClaimPda(this.programId, questPda, claimerPubkey)
+
+    // borsh layout of the program's Attestation struct
+    const body = Buffer.alloc(78)
+    claimPda.toBuffer().copy(body, 0)
+    proofHash.copy(body, 32)
+    body.writeUInt8(approve ? 0 : 1, 64)
+    body.writeUInt8(Math.round(confidence), 65)
+    body.writeUInt32LE(safetyFlags, 66)
+    body.writeBigUInt64LE(nonce, 70)
+    // the program only accepts signatures over its domain tag and id followed by the struct
+    const message = Buffer.concat([ATTESTATION_DOMAIN, this.programId.toBuffer(), body])
+
+    const ed25519Instruction = Ed25519Program.createInstructionWithPrivateKey({
+      privateKey: this.keypair.secretKey,
+      message,
+    })
+
+    return {
+      attestation: { claim: claimPda, proofHash, approve, confidence, safetyFlags, nonce },
+      ed25519Instruction,
+    }
+  }
+
   // Placeholder — in production, use Anchor Program.methods.X().instruction()
   // For now this builds the instruction manually using the IDL discriminator
   private async buildInstruction(
@@ -165,7 +362,7 @@ export class OracleService {
 
     const keys = Object.entries(accounts).map(([name, pubkey]) => ({
       pubkey,
-      isSigner: name === 'authority' || name === 'cranker',
+      isSigner: name === 'oracle' || name === 'cranker',
       isWritable: !['tokenProgram', 'systemProgram', 'rent'].includes(name),
     }))
 
@@ -182,6 +379,12 @@ export class OracleService {
       buf[8] = args.safetyFlagged ? 1 : 0
       data = buf
     }
+    if (args?.bps !== undefined) {
+      const buf = new Uint8Array(10)
+      buf.set(discriminator)
+      new DataView(buf.buffer).setUint16(8, args.bps, true)
+      data = buf
+    }
 
     return new TransactionInstruction({
       programId: this.programId,
@@ -22,10 +22,14 @@ export async function createQuest(db: Database, input: CreateQuestInput) {
   const now = Math.floor(Date.now() / 1000)
   const id = nanoid()
 
-  // Get next onchain ID from quest count
-  // In production this would come from reading the config account
-  // For now we use a DB-level counter
-  const countResult = await db.select({ count: sql<number>`count(*)` }).from(quests).get()
+  // Get the creator's next quest nonce
+  // In production this would come from reading the creator's quest_counter account
+  // For now we use a DB-level per-creator counter
+  const countResult = await db
+    .select({ count: sql<number>`count(*)` })
+    .from(quests)
+    .where(eq(quests.creatorId, input.creatorId))
+    .get()
   const onchainId = (countResult?.count ?? 0).toString()
 
   // Compute description hash
@@ -37,7 +41,8 @@ export async function createQuest(db: Database, input: CreateQuestInput) {
 
   // Derive PDAs
   const programId = new PublicKey(input.programId)
-  const [questPda] = deriveQuestPda(programId, BigInt(onchainId))
+  const creatorPubkey = new PublicKey(input.creatorPubkey)
+  const [questPda] = deriveQuestPda(programId, creatorPubkey, BigInt(onchainId))
   const [escrowPda] = deriveEscrowPda(programId, questPda)
 
   const deadline = input.timeLimitHours ? now + input.timeLimitHours * 3600 : null
@@ -143,7 +143,7 @@ async function applyDecision(
 
   } else {
     // UNCERTAIN — escalate to creator review
-    // claim.reviewDeadline is already set (24h from proof submission)
+    // claim.reviewDeadline is already set (the quest's review_deadline_hours after proof submission)
     // Creator can call POST /api/claims/:id/approve or /reject
     // If they don't respond, crank calls auto_approve
     console.log(`Claim ${claim.id}: AI uncertain (confidence ${aiResult.confidence}), escalated to creator`)
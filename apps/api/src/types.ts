@@ -33,7 +33,7 @@ export interface Env {
 
   // Internal
   WEBHOOK_SECRET: string
-  TREASURY_TOKEN_ACCOUNT: string
+  TREASURY_WALLET: string
   ENVIRONMENT: string
 }
 
@@ -1,13 +1,30 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_lang::solana_program::{ed25519_program, hash, sysvar::instructions as ix_sysvar};
+use anchor_lang::system_program::{self, CreateAccount};
+use anchor_spl::token::{self, spl_token::native_mint};
+use anchor_spl::token_2022::{self, spl_token_2022::extension::transfer_fee::TransferFeeConfig};
+use anchor_spl::token_interface::{
+    self, Burn, CloseAccount, HarvestWithheldTokensToMint, InitializeAccount3, Mint,
+    SyncNative, TokenAccount, TokenInterface, TransferChecked,
+};
 
 declare_id!("QUESTxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxx");
 
 const MIN_REWARD: u64 = 1_000_000; // 1 token (assuming 6 decimals)
 const MIN_STAKE_BPS: u64 = 500; // 5% = 500 basis points
-const DEFAULT_PROOF_DEADLINE_HOURS: u8 = 24;
-const DEFAULT_REVIEW_DEADLINE_HOURS: u8 = 24;
 const SECONDS_PER_HOUR: i64 = 3600;
+const CONFIG_TIMELOCK_SECONDS: i64 = 48 * SECONDS_PER_HOUR;
+const MAX_JURORS: usize = 16;
+const MAX_ORACLES: usize = 8;
+const MAX_TRACKED_FAILURES: usize = 8;
+const MAX_STRIKES: u8 = 3;
+const MAX_QUEST_STEPS: usize = 8;
+const MAX_GUILD_MEMBERS: usize = 8;
+const FULL_AWARD_BPS: u16 = 10000;
+const MAX_REVISIONS: u8 = 2;
+const MAX_PROOF_URI_LEN: usize = 200;
+const MAX_DESCRIPTION_LEN: usize = 280;
+const SECONDS_PER_DAY: i64 = 24 * SECONDS_PER_HOUR;
 
 #[program]
 pub mod quest {
@@ -17,20 +34,48 @@ pub mod quest {
         ctx: Context<Initialize>,
         fee_basis_points: u16,
         burn_basis_points: u16,
+        dispute: DisputeSettings,
+        deadline_bounds: DeadlineBounds,
+        rate_limits: RateLimits,
+        forfeit_split: ForfeitSplit,
     ) -> Result<()> {
         require!(fee_basis_points <= 10000, QuestError::InvalidFeeConfig);
         require!(burn_basis_points <= 10000, QuestError::InvalidFeeConfig);
+        require!(dispute.is_valid(), QuestError::InvalidDisputeSettings);
+        require!(deadline_bounds.is_valid(), QuestError::InvalidDeadlineBounds);
+        require!(rate_limits.is_valid(), QuestError::InvalidRateLimits);
+        require!(forfeit_split.is_valid(), QuestError::InvalidForfeitSplit);
 
         let config = &mut ctx.accounts.config;
         config.authority = ctx.accounts.authority.key();
+        config.pending_authority = None;
         config.treasury = ctx.accounts.treasury.key();
         config.fee_basis_points = fee_basis_points;
         config.burn_basis_points = burn_basis_points;
-        config.quest_count = 0;
+        config.dispute = dispute;
+        config.deadline_bounds = deadline_bounds;
+        config.rate_limits = rate_limits;
+        config.forfeit_split = forfeit_split;
+        config.pending_update = None;
         config.bump = ctx.bumps.config;
 
+        let juror_set = &mut ctx.accounts.juror_set;
+        juror_set.jurors = Vec::new();
+        juror_set.bump = ctx.bumps.juror_set;
+
+        // starts as a 1-of-1 quorum; the admin grows it with add_oracle
+        let oracle_set = &mut ctx.accounts.oracle_set;
+        oracle_set.oracles = vec![ctx.accounts.oracle.key()];
+        oracle_set.threshold = 1;
+        oracle_set.bump = ctx.bumps.oracle_set;
+
+        let global_stats = &mut ctx.accounts.global_stats;
+        global_stats.quest_count = 0;
+        global_stats.bump = ctx.bumps.global_stats;
+
         emit!(ProtocolInitialized {
             authority: config.authority,
+            oracle: ctx.accounts.oracle.key(),
             treasury: config.treasury,
             fee_basis_points,
         });
@@ -38,6 +83,285 @@ pub mod quest {
         Ok(())
     }
 
+    /// Authority-only: queue a config change that can be applied after the timelock
+    pub fn update_config(ctx: Context<UpdateConfig>, update: ConfigUpdate) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+
+        require!(
+            ctx.accounts.authority.key() == config.authority,
+            QuestError::NotAuthority
+        );
+        if let Some(fee) = update.fee_basis_points {
+            require!(fee <= 10000, QuestError::InvalidFeeConfig);
+        }
+        if let Some(burn) = update.burn_basis_points {
+            require!(burn <= 10000, QuestError::InvalidFeeConfig);
+        }
+        if let Some(dispute) = update.dispute {
+            require!(dispute.is_valid(), QuestError::InvalidDisputeSettings);
+        }
+        if let Some(bounds) = update.deadline_bounds {
+            require!(bounds.is_valid(), QuestError::InvalidDeadlineBounds);
+        }
+        if let Some(limits) = update.rate_limits {
+            require!(limits.is_valid(), QuestError::InvalidRateLimits);
+        }
+        if let Some(split) = update.forfeit_split {
+            require!(split.is_valid(), QuestError::InvalidForfeitSplit);
+        }
+
+        let effective_at = Clock::get()?
+            .unix_timestamp
+            .checked_add(CONFIG_TIMELOCK_SECONDS)
+            .ok_or(QuestError::Overflow)?;
+
+        config.pending_update = Some(PendingConfigUpdate {
+            update,
+            effective_at,
+        });
+
+        emit!(ConfigUpdateQueued {
+            update,
+            effective_at,
+        });
+
+        Ok(())
+    }
+
+    /// Authority-only: drop a queued config change
+    pub fn cancel_config_update(ctx: Context<UpdateConfig>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+
+        require!(
+            ctx.accounts.authority.key() == config.authority,
+            QuestError::NotAuthority
+        );
+        require!(config.pending_update.is_some(), QuestError::NoPendingConfigUpdate);
+
+        config.pending_update = None;
+
+        Ok(())
+    }
+
+    /// Permissionless crank: apply a queued config change once its timelock passes
+    pub fn apply_config_update(ctx: Context<ApplyConfigUpdate>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        let now = Clock::get()?.unix_timestamp;
+
+        let pending = config.pending_update.ok_or(QuestError::NoPendingConfigUpdate)?;
+        require!(now >= pending.effective_at, QuestError::TimelockNotElapsed);
+
+        let update = pending.update;
+        if let Some(treasury) = update.treasury {
+            config.treasury = treasury;
+        }
+        if let Some(fee) = update.fee_basis_points {
+            config.fee_basis_points = fee;
+        }
+        if let Some(burn) = update.burn_basis_points {
+            config.burn_basis_points = burn;
+        }
+        if let Some(dispute) = update.dispute {
+            config.dispute = dispute;
+        }
+        if let Some(bounds) = update.deadline_bounds {
+            config.deadline_bounds = bounds;
+        }
+        if let Some(limits) = update.rate_limits {
+            config.rate_limits = limits;
+        }
+        if let Some(split) = update.forfeit_split {
+            config.forfeit_split = split;
+        }
+        config.pending_update = None;
+
+        emit!(ConfigUpdated {
+            treasury: config.treasury,
+            fee_basis_points: config.fee_basis_points,
+            burn_basis_points: config.burn_basis_points,
+            dispute: config.dispute,
+            deadline_bounds: config.deadline_bounds,
+            rate_limits: config.rate_limits,
+            forfeit_split: config.forfeit_split,
+        });
+
+        Ok(())
+    }
+
+    /// Authority-only: nominate a new authority, who must accept before taking over
+    pub fn propose_authority(ctx: Context<UpdateConfig>, new_authority: Pubkey) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+
+        require!(
+            ctx.accounts.authority.key() == config.authority,
+            QuestError::NotAuthority
+        );
+
+        config.pending_authority = Some(new_authority);
+
+        emit!(AuthorityProposed {
+            authority: config.authority,
+            pending_authority: new_authority,
+        });
+
+        Ok(())
+    }
+
+    /// Pending authority signs to complete the handover
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+
+        require!(
+            config.pending_authority == Some(ctx.accounts.new_authority.key()),
+            QuestError::NotPendingAuthority
+        );
+
+        let previous_authority = config.authority;
+        config.authority = ctx.accounts.new_authority.key();
+        config.pending_authority = None;
+
+        emit!(AuthorityTransferred {
+            previous_authority,
+            new_authority: config.authority,
+        });
+
+        Ok(())
+    }
+
+    /// Authority-only: register a juror who can vote on disputes
+    pub fn add_juror(ctx: Context<ManageJurors>, juror: Pubkey) -> Result<()> {
+        let juror_set = &mut ctx.accounts.juror_set;
+
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.config.authority,
+            QuestError::NotAuthority
+        );
+        require!(!juror_set.jurors.contains(&juror), QuestError::AlreadyJuror);
+        require!(juror_set.jurors.len() < MAX_JURORS, QuestError::JurorSetFull);
+
+        juror_set.jurors.push(juror);
+
+        emit!(JurorAdded { juror });
+
+        Ok(())
+    }
+
+    /// Authority-only: drop a juror; votes they already cast still count
+    pub fn remove_juror(ctx: Context<ManageJurors>, juror: Pubkey) -> Result<()> {
+        let juror_set = &mut ctx.accounts.juror_set;
+
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.config.authority,
+            QuestError::NotAuthority
+        );
+        require!(juror_set.jurors.contains(&juror), QuestError::NotJuror);
+
+        juror_set.jurors.retain(|j| *j != juror);
+
+        emit!(JurorRemoved { juror });
+
+        Ok(())
+    }
+
+    /// Authority-only: add a key to the oracle quorum
+    pub fn add_oracle(ctx: Context<ManageOracles>, oracle: Pubkey) -> Result<()> {
+        let oracle_set = &mut ctx.accounts.oracle_set;
+
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.config.authority,
+            QuestError::NotAuthority
+        );
+        require!(!oracle_set.oracles.contains(&oracle), QuestError::AlreadyOracle);
+        require!(oracle_set.oracles.len() < MAX_ORACLES, QuestError::OracleSetFull);
+
+        oracle_set.oracles.push(oracle);
+
+        emit!(OracleAdded {
+            oracle,
+            threshold: oracle_set.threshold,
+        });
+
+        Ok(())
+    }
+
+    /// Authority-only: drop a key from the oracle quorum. Its pending votes stop
+    /// counting. The set can never shrink below the threshold.
+    pub fn remove_oracle(ctx: Context<ManageOracles>, oracle: Pubkey) -> Result<()> {
+        let oracle_set = &mut ctx.accounts.oracle_set;
+
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.config.authority,
+            QuestError::NotAuthority
+        );
+        require!(oracle_set.oracles.contains(&oracle), QuestError::NotOracle);
+        require!(
+            oracle_set.oracles.len() > oracle_set.threshold as usize,
+            QuestError::InvalidOracleThreshold
+        );
+
+        oracle_set.oracles.retain(|o| *o != oracle);
+
+        emit!(OracleRemoved {
+            oracle,
+            threshold: oracle_set.threshold,
+        });
+
+        Ok(())
+    }
+
+    /// Authority-only: set how many matching oracle votes settle a claim
+    pub fn set_oracle_threshold(ctx: Context<ManageOracles>, threshold: u8) -> Result<()> {
+        let oracle_set = &mut ctx.accounts.oracle_set;
+
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.config.authority,
+            QuestError::NotAuthority
+        );
+        require!(
+            threshold > 0 && threshold as usize <= oracle_set.oracles.len(),
+            QuestError::InvalidOracleThreshold
+        );
+
+        oracle_set.threshold = threshold;
+
+        emit!(OracleThresholdSet { threshold });
+
+        Ok(())
+    }
+
+    /// Authority-only: add a strike to a wallet; the third one bans it from
+    /// creating and claiming quests
+    pub fn flag_account(ctx: Context<ManageProfile>, wallet: Pubkey) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.config.authority,
+            QuestError::NotAuthority
+        );
+
+        let profile = &mut ctx.accounts.profile;
+        profile.init_if_new(wallet, ctx.bumps.profile);
+        profile.add_strike();
+
+        Ok(())
+    }
+
+    /// Authority-only: lift a ban on appeal and clear the wallet's strikes
+    pub fn unban_account(ctx: Context<ManageProfile>, wallet: Pubkey) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.config.authority,
+            QuestError::NotAuthority
+        );
+
+        let profile = &mut ctx.accounts.profile;
+        profile.init_if_new(wallet, ctx.bumps.profile);
+        profile.strikes = 0;
+        profile.banned = false;
+
+        emit!(AccountUnbanned { wallet });
+
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
     pub fn create_quest(
         ctx: Context<CreateQuest>,
         reward_amount: u64,
@@ -45,11 +369,44 @@ pub mod quest {
         target: Option<Pubkey>,
         max_claimers: u8,
         time_limit: Option<i64>,
+        proof_deadline_hours: u8,
+        review_deadline_hours: u8,
         description_hash: [u8; 32],
+        pooled: bool,
+        steps: Vec<QuestStep>,
+        description: Option<String>,
     ) -> Result<()> {
         require!(reward_amount >= MIN_REWARD, QuestError::RewardTooLow);
         require!(max_claimers > 0 && max_claimers <= 100, QuestError::InvalidMaxClaimers);
 
+        // Pooled quests pay reward_amount to each of max_claimers winners; any
+        // other quest has a single reward, so it can only take one claimer
+        if pooled {
+            require!(quest_type == QuestType::Open, QuestError::PooledQuestMustBeOpen);
+        } else {
+            require!(max_claimers == 1, QuestError::InvalidMaxClaimers);
+        }
+
+        // Chain quests pay one claimer step by step, so they take a single slot
+        if !steps.is_empty() {
+            let total_bps = steps.iter().map(|step| step.share_bps as u32).sum::<u32>();
+            require!(
+                (2..=MAX_QUEST_STEPS).contains(&steps.len())
+                    && steps.iter().all(|step| step.share_bps > 0)
+                    && total_bps == 10000
+                    && !pooled
+                    && max_claimers == 1,
+                QuestError::InvalidQuestSteps
+            );
+        }
+        let escrow_amount = if pooled {
+            reward_amount
+                .checked_mul(max_claimers as u64)
+                .ok_or(QuestError::Overflow)?
+        } else {
+            reward_amount
+        };
+
         if quest_type == QuestType::Direct {
             let t = target.ok_or(QuestError::DirectQuestNeedsTarget)?;
             require!(t != ctx.accounts.creator.key(), QuestError::CannotTargetSelf);
@@ -62,63 +419,135 @@ pub mod quest {
             );
         }
 
-        let config = &mut ctx.accounts.config;
+        let config = &ctx.accounts.config;
         let quest = &mut ctx.accounts.quest;
 
-        quest.id = config.quest_count;
+        let bounds = &config.deadline_bounds;
+        require!(
+            (bounds.min_proof_hours..=bounds.max_proof_hours).contains(&proof_deadline_hours),
+            QuestError::InvalidDeadline
+        );
+        require!(
+            (bounds.min_review_hours..=bounds.max_review_hours).contains(&review_deadline_hours),
+            QuestError::InvalidDeadline
+        );
+
+        let profile = &mut ctx.accounts.profile;
+        profile.init_if_new(ctx.accounts.creator.key(), ctx.bumps.profile);
+        require!(!profile.banned, QuestError::AccountBanned);
+        profile.record_quest(Clock::get()?.unix_timestamp, config.rate_limits.max_quests_per_day)?;
+
+        let stats = &mut ctx.accounts.creator_stats;
+        stats.init_if_new(ctx.accounts.creator.key(), ctx.bumps.creator_stats);
+        stats.quests_posted = stats.quests_posted
+            .checked_add(1)
+            .ok_or(QuestError::Overflow)?;
+
+        let counter = &mut ctx.accounts.quest_counter;
+        counter.init_if_new(ctx.accounts.creator.key(), ctx.bumps.quest_counter);
+        quest.id = counter.next_nonce;
+        counter.next_nonce = counter.next_nonce
+            .checked_add(1)
+            .ok_or(QuestError::Overflow)?;
+
+        // Only writers who pass the shared stats account contend on it
+        if let Some(global_stats) = ctx.accounts.global_stats.as_mut() {
+            global_stats.quest_count = global_stats.quest_count
+                .checked_add(1)
+                .ok_or(QuestError::Overflow)?;
+        }
+
         quest.creator = ctx.accounts.creator.key();
         quest.escrow = ctx.accounts.escrow.key();
         quest.reward_mint = ctx.accounts.reward_mint.key();
-        quest.reward_amount = reward_amount;
         quest.quest_type = quest_type;
         quest.status = QuestStatus::Active;
         quest.target = target;
         quest.max_claimers = max_claimers;
         quest.current_claimers = 0;
+        quest.pooled = pooled;
+        quest.completed_claimers = 0;
         quest.time_limit = time_limit;
-        quest.proof_deadline_hours = DEFAULT_PROOF_DEADLINE_HOURS;
-        quest.review_deadline_hours = DEFAULT_REVIEW_DEADLINE_HOURS;
+        quest.proof_deadline_hours = proof_deadline_hours;
+        quest.review_deadline_hours = review_deadline_hours;
         quest.description_hash = description_hash;
+        quest.steps = steps;
+        quest.steps_paid = 0;
+        quest.contributed_amount = 0;
+        quest.contributors = 0;
+        quest.contributor_refund_pool = 0;
+        quest.refunds_ready = false;
         quest.created_at = Clock::get()?.unix_timestamp;
         quest.bump = ctx.bumps.quest;
 
-        config.quest_count = config.quest_count
-            .checked_add(1)
-            .ok_or(QuestError::Overflow)?;
-
-        token::transfer(
-            CpiContext::new(
-                ctx.accounts.token_program.to_account_info(),
-                Transfer {
-                    from: ctx.accounts.creator_token_account.to_account_info(),
-                    to: ctx.accounts.escrow.to_account_info(),
-                    authority: ctx.accounts.creator.to_account_info(),
-                },
-            ),
-            reward_amount,
+        let received = fund_escrow(
+            &ctx.accounts.token_program,
+            &ctx.accounts.system_program,
+            &mut ctx.accounts.escrow,
+            &ctx.accounts.reward_mint,
+            &ctx.accounts.creator,
+            ctx.accounts.creator_token_account.as_ref(),
+            escrow_amount,
         )?;
 
+        // Transfer-fee mints deliver less than was sent; record what escrow holds
+        quest.reward_amount = if pooled {
+            received
+                .checked_div(max_claimers as u64)
+                .ok_or(QuestError::Overflow)?
+        } else {
+            received
+        };
+        quest.creator_funded = received;
+        require!(quest.reward_amount >= MIN_REWARD, QuestError::RewardTooLow);
+
+        // Optional on-chain copy of the dare; borsh already rejects invalid UTF-8
+        match (ctx.accounts.quest_description.as_mut(), description) {
+            (Some(stored), Some(text)) => {
+                require!(text.len() <= MAX_DESCRIPTION_LEN, QuestError::DescriptionTooLong);
+                require!(
+                    hash::hash(text.as_bytes()).to_bytes() == description_hash,
+                    QuestError::DescriptionHashMismatch
+                );
+                stored.quest = quest.key();
+                stored.creator = quest.creator;
+                stored.text = text;
+                stored.bump = ctx.bumps.quest_description.ok_or(QuestError::MissingDescriptionBump)?;
+            }
+            (None, None) => {}
+            _ => return err!(QuestError::DescriptionMismatch),
+        }
+
         emit!(QuestCreated {
             quest_id: quest.id,
+            quest: quest.key(),
             creator: quest.creator,
-            reward_amount,
+            reward_amount: quest.reward_amount,
             reward_mint: quest.reward_mint,
             quest_type,
+            pooled,
         });
 
         Ok(())
     }
 
+    /// members: empty for a solo claim. For a guild claim, the leader (the
+    /// signer, listed first) posts their weighted share of the minimum stake
+    /// and the claim waits in Forming until every member has joined.
     pub fn claim_quest(
         ctx: Context<ClaimQuest>,
         stake_amount: u64,
+        members: Vec<GuildShare>,
     ) -> Result<()> {
         let quest = &mut ctx.accounts.quest;
         let claim = &mut ctx.accounts.claim;
         let now = Clock::get()?.unix_timestamp;
 
         require!(quest.status == QuestStatus::Active, QuestError::QuestNotActive);
-        require!(quest.current_claimers < quest.max_claimers, QuestError::QuestFull);
+        let taken_slots = quest.current_claimers
+            .checked_add(quest.completed_claimers)
+            .ok_or(QuestError::Overflow)?;
+        require!(taken_slots < quest.max_claimers, QuestError::QuestFull);
 
         require!(
             quest.creator != ctx.accounts.claimer.key(),
@@ -136,53 +565,145 @@ pub mod quest {
             require!(now < limit, QuestError::QuestExpired);
         }
 
-        let min_stake = quest.reward_amount
-            .checked_mul(MIN_STAKE_BPS)
-            .ok_or(QuestError::Overflow)?
-            .checked_div(10000)
+        let limits = &ctx.accounts.config.rate_limits;
+        let profile = &mut ctx.accounts.profile;
+        profile.init_if_new(ctx.accounts.claimer.key(), ctx.bumps.profile);
+        require!(!profile.banned, QuestError::AccountBanned);
+        require!(
+            profile.active_claims < limits.max_active_claims,
+            QuestError::TooManyActiveClaims
+        );
+        require!(
+            profile.recent_failures(now, limits.failure_window_seconds) < limits.max_recent_failures,
+            QuestError::TooManyRecentFailures
+        );
+        profile.active_claims = profile.active_claims
+            .checked_add(1)
             .ok_or(QuestError::Overflow)?;
+        ctx.accounts.claimer_stats.init_if_new(ctx.accounts.claimer.key(), ctx.bumps.claimer_stats);
+
+        let guild = !members.is_empty();
+        let min_stake = if guild {
+            validate_guild(&members, ctx.accounts.claimer.key(), quest.creator)?;
+            guild_stake(quest.min_stake()?, members[0].weight_bps)?
+        } else {
+            quest.min_stake()?
+        };
         require!(stake_amount >= min_stake, QuestError::StakeTooLow);
 
         claim.quest = quest.key();
         claim.claimer = ctx.accounts.claimer.key();
-        claim.stake_amount = stake_amount;
-        claim.status = ClaimStatus::Active;
-        claim.proof_deadline = now
-            .checked_add(
-                (quest.proof_deadline_hours as i64)
-                    .checked_mul(SECONDS_PER_HOUR)
-                    .ok_or(QuestError::Overflow)?
-            )
-            .ok_or(QuestError::Overflow)?;
+        claim.status = if guild { ClaimStatus::Forming } else { ClaimStatus::Active };
+        claim.members = members
+            .iter()
+            .map(|share| GuildMember {
+                wallet: share.wallet,
+                weight_bps: share.weight_bps,
+                stake: 0,
+                joined: false,
+            })
+            .collect();
+        claim.proof_deadline = quest.proof_deadline_from(now)?;
         claim.review_deadline = None;
+        claim.dispute_deadline = None;
+        claim.safety_flagged = false;
+        claim.oracle_votes = Vec::new();
+        claim.attestation_nonce = 0;
         claim.proof_hash = None;
         claim.claimed_at = now;
         claim.submitted_at = None;
+        claim.current_step = 0;
+        claim.revisions = 0;
         claim.bump = ctx.bumps.claim;
 
         quest.current_claimers = quest.current_claimers
             .checked_add(1)
             .ok_or(QuestError::Overflow)?;
-        if quest.current_claimers >= quest.max_claimers {
+        if taken_slots + 1 >= quest.max_claimers {
             quest.status = QuestStatus::Claimed;
         }
 
-        token::transfer(
-            CpiContext::new(
-                ctx.accounts.token_program.to_account_info(),
-                Transfer {
-                    from: ctx.accounts.claimer_token_account.to_account_info(),
-                    to: ctx.accounts.escrow.to_account_info(),
-                    authority: ctx.accounts.claimer.to_account_info(),
-                },
-            ),
+        let received = fund_escrow(
+            &ctx.accounts.token_program,
+            &ctx.accounts.system_program,
+            &mut ctx.accounts.escrow,
+            &ctx.accounts.reward_mint,
+            &ctx.accounts.claimer,
+            ctx.accounts.claimer_token_account.as_ref(),
             stake_amount,
         )?;
 
+        // Transfer-fee mints deliver less than was sent; record what escrow holds
+        require!(received >= min_stake, QuestError::StakeTooLow);
+        claim.stake_amount = received;
+        if let Some(leader) = claim.members.first_mut() {
+            leader.stake = received;
+            leader.joined = true;
+        }
+
         emit!(QuestClaimed {
             quest_id: quest.id,
+            quest: quest.key(),
             claimer: claim.claimer,
+            stake_amount: received,
+        });
+
+        Ok(())
+    }
+
+    /// Guild member posts their weighted share of the minimum stake. The claim
+    /// goes Active once everyone has joined; the proof deadline runs from the
+    /// original claim either way.
+    pub fn join_guild(ctx: Context<JoinGuild>, stake_amount: u64) -> Result<()> {
+        let quest = &ctx.accounts.quest;
+        let claim = &mut ctx.accounts.claim;
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(claim.status == ClaimStatus::Forming, QuestError::GuildNotForming);
+        require!(now <= claim.proof_deadline, QuestError::ProofDeadlinePassed);
+
+        let member_key = ctx.accounts.member.key();
+        let index = claim.members
+            .iter()
+            .position(|m| m.wallet == member_key)
+            .ok_or(QuestError::NotGuildMember)?;
+        require!(!claim.members[index].joined, QuestError::AlreadyJoinedGuild);
+
+        // Banned wallets stay out of guilds too. Claim counts and failures are
+        // only kept on the leader's profile, which carries the claim.
+        let profile = &mut ctx.accounts.profile;
+        profile.init_if_new(member_key, ctx.bumps.profile);
+        require!(!profile.banned, QuestError::AccountBanned);
+
+        let min_stake = guild_stake(quest.min_stake()?, claim.members[index].weight_bps)?;
+        require!(stake_amount >= min_stake, QuestError::StakeTooLow);
+
+        let received = fund_escrow(
+            &ctx.accounts.token_program,
+            &ctx.accounts.system_program,
+            &mut ctx.accounts.escrow,
+            &ctx.accounts.reward_mint,
+            &ctx.accounts.member,
+            ctx.accounts.member_token_account.as_ref(),
             stake_amount,
+        )?;
+        require!(received >= min_stake, QuestError::StakeTooLow);
+
+        claim.members[index].stake = received;
+        claim.members[index].joined = true;
+        claim.stake_amount = claim.stake_amount
+            .checked_add(received)
+            .ok_or(QuestError::Overflow)?;
+        if claim.members.iter().all(|m| m.joined) {
+            claim.status = ClaimStatus::Active;
+        }
+
+        emit!(GuildMemberJoined {
+            quest_id: quest.id,
+            quest: quest.key(),
+            claimer: claim.claimer,
+            member: member_key,
+            stake_amount: received,
         });
 
         Ok(())
@@ -191,17 +712,31 @@ pub mod quest {
     pub fn submit_proof(
         ctx: Context<SubmitProof>,
         proof_hash: [u8; 32],
+        uri: String,
+        duration_seconds: u32,
+        frame_root: [u8; 32],
     ) -> Result<()> {
         let quest = &ctx.accounts.quest;
         let claim = &mut ctx.accounts.claim;
         let now = Clock::get()?.unix_timestamp;
 
-        require!(claim.status == ClaimStatus::Active, QuestError::ClaimNotActive);
+        require!(
+            !uri.is_empty() && uri.len() <= MAX_PROOF_URI_LEN,
+            QuestError::InvalidProofUri
+        );
+        require!(duration_seconds > 0, QuestError::InvalidProofDuration);
+
+        require!(
+            matches!(claim.status, ClaimStatus::Active | ClaimStatus::NeedsRevision),
+            QuestError::ClaimNotActive
+        );
         require!(claim.claimer == ctx.accounts.claimer.key(), QuestError::NotClaimer);
         require!(now <= claim.proof_deadline, QuestError::ProofDeadlinePassed);
 
         claim.proof_hash = Some(proof_hash);
         claim.status = ClaimStatus::Submitted;
+        let profile = &mut ctx.accounts.profile;
+        profile.active_claims = profile.active_claims.saturating_sub(1);
         claim.submitted_at = Some(now);
         claim.review_deadline = Some(
             now.checked_add(
@@ -212,93 +747,200 @@ pub mod quest {
             .ok_or(QuestError::Overflow)?
         );
 
+        // Overwritten on every submission, so it always describes the proof
+        // currently under review
+        let proof = &mut ctx.accounts.proof;
+        proof.claim = claim.key();
+        proof.claimer = claim.claimer;
+        proof.step = claim.current_step;
+        proof.proof_hash = proof_hash;
+        proof.uri = uri.clone();
+        proof.duration_seconds = duration_seconds;
+        proof.frame_root = frame_root;
+        proof.submitted_at = now;
+        proof.bump = ctx.bumps.proof;
+
         emit!(ProofSubmitted {
             quest_id: quest.id,
+            quest: quest.key(),
             claimer: claim.claimer,
+            step: claim.current_step,
             proof_hash,
+            uri,
+            duration_seconds,
+            frame_root,
         });
 
         Ok(())
     }
 
-    /// Oracle-only: approve after AI or creator verification
-    pub fn approve_completion(ctx: Context<ApproveCompletion>) -> Result<()> {
+    /// Oracle-only: vote to approve after AI or creator verification. Pays out
+    /// once the oracle set's threshold of approve votes is reached.
+    pub fn approve_completion<'info>(ctx: Context<'_, '_, '_, 'info, ApproveCompletion<'info>>) -> Result<()> {
         let quest = &mut ctx.accounts.quest;
         let claim = &mut ctx.accounts.claim;
         let config = &ctx.accounts.config;
 
         require!(claim.status == ClaimStatus::Submitted, QuestError::ClaimNotSubmitted);
-        require!(
-            ctx.accounts.authority.key() == config.authority,
-            QuestError::NotOracle
-        );
+        let quorum = record_oracle_vote(
+            &ctx.accounts.oracle_set,
+            quest,
+            claim,
+            ctx.accounts.oracle.key(),
+            OracleVerdict::Approve,
+        )?;
+        if !quorum {
+            return Ok(());
+        }
 
-        let fee_amount = quest.reward_amount
-            .checked_mul(config.fee_basis_points as u64)
-            .ok_or(QuestError::Overflow)?
-            .checked_div(10000)
-            .ok_or(QuestError::Overflow)?;
-        let reward_after_fee = quest.reward_amount
-            .checked_sub(fee_amount)
-            .ok_or(QuestError::Overflow)?;
+        let quest_creator = quest.creator;
 
         let quest_seeds = &[
-            b"quest",
+            b"quest".as_ref(),
+            quest_creator.as_ref(),
             &quest.id.to_le_bytes(),
             &[quest.bump],
         ];
         let signer_seeds = &[&quest_seeds[..]];
+        let payout = EscrowPayout {
+            quest: quest.to_account_info(),
+            signer_seeds,
+            escrow: &ctx.accounts.escrow,
+            reward_mint: &ctx.accounts.reward_mint,
+            unwrap_account: &ctx.accounts.unwrap_account,
+            unwrap_bump: ctx.bumps.unwrap_account,
+            payer: ctx.accounts.oracle.to_account_info(),
+            token_program: &ctx.accounts.token_program,
+            system_program: &ctx.accounts.system_program,
+        };
+        let parties = SettlementParties {
+            creator: ctx.accounts.creator.to_account_info(),
+            creator_token_account: ctx.accounts.creator_token_account.as_ref(),
+            claimer: ctx.accounts.claimer.to_account_info(),
+            claimer_token_account: ctx.accounts.claimer_token_account.as_ref(),
+            members: ctx.remaining_accounts,
+        };
+
+        approve_claim(
+            config,
+            quest,
+            claim,
+            &payout,
+            &parties,
+            ctx.accounts.treasury.to_account_info(),
+            &mut SettlementStats {
+                creator: &mut ctx.accounts.creator_stats,
+                claimer: &mut ctx.accounts.claimer_stats,
+                profile: &mut ctx.accounts.profile,
+            },
+            FULL_AWARD_BPS,
+        )
+    }
 
-        let claimer_payout = reward_after_fee
-            .checked_add(claim.stake_amount)
-            .ok_or(QuestError::Overflow)?;
+    /// Oracle-only: vote to award partial credit. Once the threshold of votes
+    /// for the same `bps` is reached, pays that share of the reward after fees,
+    /// refunds the rest to the creator and returns the stake.
+    pub fn settle_partial<'info>(ctx: Context<'_, '_, '_, 'info, ApproveCompletion<'info>>, bps: u16) -> Result<()> {
+        let quest = &mut ctx.accounts.quest;
+        let claim = &mut ctx.accounts.claim;
+        let config = &ctx.accounts.config;
 
-        token::transfer(
-            CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                Transfer {
-                    from: ctx.accounts.escrow.to_account_info(),
-                    to: ctx.accounts.claimer_token_account.to_account_info(),
-                    authority: quest.to_account_info(),
-                },
-                signer_seeds,
-            ),
-            claimer_payout,
+        require!(claim.status == ClaimStatus::Submitted, QuestError::ClaimNotSubmitted);
+        require!(bps > 0 && bps < FULL_AWARD_BPS, QuestError::InvalidAwardBps);
+        require!(quest.steps.is_empty(), QuestError::PartialChainStep);
+        let quorum = record_oracle_vote(
+            &ctx.accounts.oracle_set,
+            quest,
+            claim,
+            ctx.accounts.oracle.key(),
+            OracleVerdict::Partial { bps },
         )?;
-
-        if fee_amount > 0 {
-            token::transfer(
-                CpiContext::new_with_signer(
-                    ctx.accounts.token_program.to_account_info(),
-                    Transfer {
-                        from: ctx.accounts.escrow.to_account_info(),
-                        to: ctx.accounts.treasury.to_account_info(),
-                        authority: quest.to_account_info(),
-                    },
-                    signer_seeds,
-                ),
-                fee_amount,
-            )?;
+        if !quorum {
+            return Ok(());
         }
 
-        claim.status = ClaimStatus::Approved;
-        quest.status = QuestStatus::Completed;
+        let quest_creator = quest.creator;
 
-        emit!(QuestCompleted {
-            quest_id: quest.id,
-            claimer: claim.claimer,
-            reward_amount: reward_after_fee,
-            fee_amount,
-        });
-
-        Ok(())
+        let quest_seeds = &[
+            b"quest".as_ref(),
+            quest_creator.as_ref(),
+            &quest.id.to_le_bytes(),
+            &[quest.bump],
+        ];
+        let signer_seeds = &[&quest_seeds[..]];
+        let payout = EscrowPayout {
+            quest: quest.to_account_info(),
+            signer_seeds,
+            escrow: &ctx.accounts.escrow,
+            reward_mint: &ctx.accounts.reward_mint,
+            unwrap_account: &ctx.accounts.unwrap_account,
+            unwrap_bump: ctx.bumps.unwrap_account,
+            payer: ctx.accounts.oracle.to_account_info(),
+            token_program: &ctx.accounts.token_program,
+            system_program: &ctx.accounts.system_program,
+        };
+        let parties = SettlementParties {
+            creator: ctx.accounts.creator.to_account_info(),
+            creator_token_account: ctx.accounts.creator_token_account.as_ref(),
+            claimer: ctx.accounts.claimer.to_account_info(),
+            claimer_token_account: ctx.accounts.claimer_token_account.as_ref(),
+            members: ctx.remaining_accounts,
+        };
+
+        approve_claim(
+            config,
+            quest,
+            claim,
+            &payout,
+            &parties,
+            ctx.accounts.treasury.to_account_info(),
+            &mut SettlementStats {
+                creator: &mut ctx.accounts.creator_stats,
+                claimer: &mut ctx.accounts.claimer_stats,
+                profile: &mut ctx.accounts.profile,
+            },
+            bps,
+        )
     }
 
-    /// Oracle-only: reject completion
-    /// safety_flagged: if true, return stake to claimer (content issue, not their fault)
+    /// Oracle-only: vote to reject completion. Once the threshold of matching
+    /// votes is reached, funds stay in escrow until the dispute window closes
+    /// (finalize_rejection) or a dispute is resolved.
+    /// safety_flagged: if true, return stake to claimer (content issue, not their fault);
+    /// the claimer still takes a strike once the rejection is final
     pub fn reject_completion(
         ctx: Context<RejectCompletion>,
         safety_flagged: bool,
+    ) -> Result<()> {
+        let claim = &mut ctx.accounts.claim;
+        let config = &ctx.accounts.config;
+
+        require!(claim.status == ClaimStatus::Submitted, QuestError::ClaimNotSubmitted);
+        let verdict = if safety_flagged {
+            OracleVerdict::SafetyReject
+        } else {
+            OracleVerdict::Reject
+        };
+        let quorum = record_oracle_vote(
+            &ctx.accounts.oracle_set,
+            &ctx.accounts.quest,
+            claim,
+            ctx.accounts.oracle.key(),
+            verdict,
+        )?;
+        if !quorum {
+            return Ok(());
+        }
+
+        mark_rejected(&ctx.accounts.quest, claim, safety_flagged, &config.dispute)
+    }
+
+    /// Permissionless relay: approve with oracle-signed attestations carried in
+    /// the preceding Ed25519 instruction. Each valid signature counts as that
+    /// oracle's vote; pays out once the threshold is reached.
+    pub fn approve_with_attestation<'info>(
+        ctx: Context<'_, '_, '_, 'info, ApproveWithAttestation<'info>>,
+        attestation: Attestation,
     ) -> Result<()> {
         let quest = &mut ctx.accounts.quest;
         let claim = &mut ctx.accounts.claim;
@@ -306,116 +948,184 @@ pub mod quest {
 
         require!(claim.status == ClaimStatus::Submitted, QuestError::ClaimNotSubmitted);
         require!(
-            ctx.accounts.authority.key() == config.authority,
-            QuestError::NotOracle
+            attestation.decision == AttestationDecision::Approve,
+            QuestError::AttestationMismatch
         );
+        let signers = attestation_signers(&ctx.accounts.instructions, &attestation)?;
+        let quorum = record_attestation(
+            &ctx.accounts.oracle_set,
+            quest,
+            claim,
+            &attestation,
+            &signers,
+        )?;
+        if !quorum {
+            return Ok(());
+        }
+
+        let quest_creator = quest.creator;
 
         let quest_seeds = &[
-            b"quest",
+            b"quest".as_ref(),
+            quest_creator.as_ref(),
             &quest.id.to_le_bytes(),
             &[quest.bump],
         ];
         let signer_seeds = &[&quest_seeds[..]];
+        let payout = EscrowPayout {
+            quest: quest.to_account_info(),
+            signer_seeds,
+            escrow: &ctx.accounts.escrow,
+            reward_mint: &ctx.accounts.reward_mint,
+            unwrap_account: &ctx.accounts.unwrap_account,
+            unwrap_bump: ctx.bumps.unwrap_account,
+            payer: ctx.accounts.relayer.to_account_info(),
+            token_program: &ctx.accounts.token_program,
+            system_program: &ctx.accounts.system_program,
+        };
+        let parties = SettlementParties {
+            creator: ctx.accounts.creator.to_account_info(),
+            creator_token_account: ctx.accounts.creator_token_account.as_ref(),
+            claimer: ctx.accounts.claimer.to_account_info(),
+            claimer_token_account: ctx.accounts.claimer_token_account.as_ref(),
+            members: ctx.remaining_accounts,
+        };
+
+        approve_claim(
+            config,
+            quest,
+            claim,
+            &payout,
+            &parties,
+            ctx.accounts.treasury.to_account_info(),
+            &mut SettlementStats {
+                creator: &mut ctx.accounts.creator_stats,
+                claimer: &mut ctx.accounts.claimer_stats,
+                profile: &mut ctx.accounts.profile,
+            },
+            FULL_AWARD_BPS,
+        )
+    }
 
-        if safety_flagged {
-            // Safety rejection: reward back to creator, stake back to claimer
-            token::transfer(
-                CpiContext::new_with_signer(
-                    ctx.accounts.token_program.to_account_info(),
-                    Transfer {
-                        from: ctx.accounts.escrow.to_account_info(),
-                        to: ctx.accounts.creator_token_account.to_account_info(),
-                        authority: quest.to_account_info(),
-                    },
-                    signer_seeds,
-                ),
-                quest.reward_amount,
-            )?;
-
-            if claim.stake_amount > 0 {
-                token::transfer(
-                    CpiContext::new_with_signer(
-                        ctx.accounts.token_program.to_account_info(),
-                        Transfer {
-                            from: ctx.accounts.escrow.to_account_info(),
-                            to: ctx.accounts.claimer_token_account.to_account_info(),
-                            authority: quest.to_account_info(),
-                        },
-                        signer_seeds,
-                    ),
-                    claim.stake_amount,
-                )?;
-            }
-        } else {
-            // Normal rejection: reward + stake go to creator
-            let creator_payout = quest.reward_amount
-                .checked_add(claim.stake_amount)
-                .ok_or(QuestError::Overflow)?;
+    /// Permissionless relay: reject with oracle-signed attestations carried in
+    /// the preceding Ed25519 instruction. Any safety flag set in the attestation
+    /// returns the stake, as with reject_completion(safety_flagged = true).
+    pub fn reject_with_attestation(
+        ctx: Context<RejectWithAttestation>,
+        attestation: Attestation,
+    ) -> Result<()> {
+        let claim = &mut ctx.accounts.claim;
+        let config = &ctx.accounts.config;
 
-            token::transfer(
-                CpiContext::new_with_signer(
-                    ctx.accounts.token_program.to_account_info(),
-                    Transfer {
-                        from: ctx.accounts.escrow.to_account_info(),
-                        to: ctx.accounts.creator_token_account.to_account_info(),
-                        authority: quest.to_account_info(),
-                    },
-                    signer_seeds,
-                ),
-                creator_payout,
-            )?;
+        require!(claim.status == ClaimStatus::Submitted, QuestError::ClaimNotSubmitted);
+        require!(
+            attestation.decision == AttestationDecision::Reject,
+            QuestError::AttestationMismatch
+        );
+        let signers = attestation_signers(&ctx.accounts.instructions, &attestation)?;
+        let quorum = record_attestation(
+            &ctx.accounts.oracle_set,
+            &ctx.accounts.quest,
+            claim,
+            &attestation,
+            &signers,
+        )?;
+        if !quorum {
+            return Ok(());
         }
 
-        claim.status = ClaimStatus::Rejected;
-        quest.status = QuestStatus::Failed;
-
-        emit!(QuestFailed {
-            quest_id: quest.id,
-            claimer: claim.claimer,
-            reason: FailReason::Rejected,
-        });
-
-        Ok(())
+        mark_rejected(
+            &ctx.accounts.quest,
+            claim,
+            attestation.safety_flags != 0,
+            &config.dispute,
+        )
     }
 
-    /// Claimer voluntarily abandons — forfeits stake to creator
+    /// Claimer voluntarily abandons — forfeits stake per the config split
     pub fn abandon_claim(ctx: Context<AbandonClaim>) -> Result<()> {
         let quest = &mut ctx.accounts.quest;
         let claim = &mut ctx.accounts.claim;
 
-        require!(claim.status == ClaimStatus::Active, QuestError::ClaimNotActive);
+        require!(
+            matches!(
+                claim.status,
+                ClaimStatus::Active | ClaimStatus::Forming | ClaimStatus::NeedsRevision
+            ),
+            QuestError::ClaimNotActive
+        );
         require!(claim.claimer == ctx.accounts.claimer.key(), QuestError::NotClaimer);
 
+        let quest_creator = quest.creator;
+
         let quest_seeds = &[
-            b"quest",
+            b"quest".as_ref(),
+            quest_creator.as_ref(),
             &quest.id.to_le_bytes(),
             &[quest.bump],
         ];
         let signer_seeds = &[&quest_seeds[..]];
+        let payout = EscrowPayout {
+            quest: quest.to_account_info(),
+            signer_seeds,
+            escrow: &ctx.accounts.escrow,
+            reward_mint: &ctx.accounts.reward_mint,
+            unwrap_account: &ctx.accounts.unwrap_account,
+            unwrap_bump: ctx.bumps.unwrap_account,
+            payer: ctx.accounts.claimer.to_account_info(),
+            token_program: &ctx.accounts.token_program,
+            system_program: &ctx.accounts.system_program,
+        };
 
         if claim.stake_amount > 0 {
-            token::transfer(
-                CpiContext::new_with_signer(
-                    ctx.accounts.token_program.to_account_info(),
-                    Transfer {
-                        from: ctx.accounts.escrow.to_account_info(),
-                        to: ctx.accounts.creator_token_account.to_account_info(),
-                        authority: quest.to_account_info(),
-                    },
-                    signer_seeds,
-                ),
-                claim.stake_amount,
+            let creator_share = forfeit_stake(
+                &ctx.accounts.config,
+                quest,
+                claim,
+                &payout,
+                ctx.accounts.treasury.to_account_info(),
+            )?;
+            payout.pay(
+                ctx.accounts.creator_token_account.as_ref(),
+                &ctx.accounts.creator,
+                creator_share,
             )?;
         }
 
         claim.status = ClaimStatus::Abandoned;
+        ctx.accounts.profile.record_failure(Clock::get()?.unix_timestamp);
+        let stats = &mut ctx.accounts.claimer_stats;
+        stats.claims_abandoned = stats.claims_abandoned
+            .checked_add(1)
+            .ok_or(QuestError::Overflow)?;
         quest.current_claimers = quest.current_claimers.saturating_sub(1);
-        if quest.status == QuestStatus::Claimed {
+        if claim.current_step > 0 {
+            // A chain quest that has paid out steps cannot restart for someone
+            // else; the unpaid remainder goes back to the creator
+            quest.status = QuestStatus::Failed;
+            close_quest_accounts(
+                quest,
+                &payout,
+                ctx.accounts.creator.to_account_info(),
+                ctx.accounts.creator_token_account.as_ref(),
+                None,
+            )?;
+        } else if quest.status == QuestStatus::Expired && quest.current_claimers == 0 {
+            // The last live claim on an expired quest closes it
+            close_quest_accounts(
+                quest,
+                &payout,
+                ctx.accounts.creator.to_account_info(),
+                ctx.accounts.creator_token_account.as_ref(),
+                None,
+            )?;
+        } else if quest.status == QuestStatus::Claimed {
             quest.status = QuestStatus::Active;
         }
 
         emit!(ClaimAbandoned {
             quest_id: quest.id,
+            quest: quest.key(),
             claimer: claim.claimer,
         });
 
@@ -428,39 +1138,85 @@ pub mod quest {
         let claim = &mut ctx.accounts.claim;
         let now = Clock::get()?.unix_timestamp;
 
-        require!(claim.status == ClaimStatus::Active, QuestError::ClaimNotActive);
+        require!(
+            matches!(
+                claim.status,
+                ClaimStatus::Active | ClaimStatus::Forming | ClaimStatus::NeedsRevision
+            ),
+            QuestError::ClaimNotActive
+        );
         require!(now > claim.proof_deadline, QuestError::DeadlineNotReached);
 
+        let quest_creator = quest.creator;
+
         let quest_seeds = &[
-            b"quest",
+            b"quest".as_ref(),
+            quest_creator.as_ref(),
             &quest.id.to_le_bytes(),
             &[quest.bump],
         ];
         let signer_seeds = &[&quest_seeds[..]];
+        let payout = EscrowPayout {
+            quest: quest.to_account_info(),
+            signer_seeds,
+            escrow: &ctx.accounts.escrow,
+            reward_mint: &ctx.accounts.reward_mint,
+            unwrap_account: &ctx.accounts.unwrap_account,
+            unwrap_bump: ctx.bumps.unwrap_account,
+            payer: ctx.accounts.cranker.to_account_info(),
+            token_program: &ctx.accounts.token_program,
+            system_program: &ctx.accounts.system_program,
+        };
 
         if claim.stake_amount > 0 {
-            token::transfer(
-                CpiContext::new_with_signer(
-                    ctx.accounts.token_program.to_account_info(),
-                    Transfer {
-                        from: ctx.accounts.escrow.to_account_info(),
-                        to: ctx.accounts.creator_token_account.to_account_info(),
-                        authority: quest.to_account_info(),
-                    },
-                    signer_seeds,
-                ),
-                claim.stake_amount,
+            let creator_share = forfeit_stake(
+                &ctx.accounts.config,
+                quest,
+                claim,
+                &payout,
+                ctx.accounts.treasury.to_account_info(),
+            )?;
+            payout.pay(
+                ctx.accounts.creator_token_account.as_ref(),
+                &ctx.accounts.creator,
+                creator_share,
             )?;
         }
 
         claim.status = ClaimStatus::Expired;
+        ctx.accounts.profile.record_failure(now);
+        let stats = &mut ctx.accounts.claimer_stats;
+        stats.claims_expired = stats.claims_expired
+            .checked_add(1)
+            .ok_or(QuestError::Overflow)?;
         quest.current_claimers = quest.current_claimers.saturating_sub(1);
-        if quest.status == QuestStatus::Claimed {
+        if claim.current_step > 0 {
+            // A chain quest that has paid out steps cannot restart for someone
+            // else; the unpaid remainder goes back to the creator
+            quest.status = QuestStatus::Failed;
+            close_quest_accounts(
+                quest,
+                &payout,
+                ctx.accounts.creator.to_account_info(),
+                ctx.accounts.creator_token_account.as_ref(),
+                None,
+            )?;
+        } else if quest.status == QuestStatus::Expired && quest.current_claimers == 0 {
+            // The last live claim on an expired quest closes it
+            close_quest_accounts(
+                quest,
+                &payout,
+                ctx.accounts.creator.to_account_info(),
+                ctx.accounts.creator_token_account.as_ref(),
+                None,
+            )?;
+        } else if quest.status == QuestStatus::Claimed {
             quest.status = QuestStatus::Active;
         }
 
         emit!(ClaimExpired {
             quest_id: quest.id,
+            quest: quest.key(),
             claimer: claim.claimer,
         });
 
@@ -468,7 +1224,7 @@ pub mod quest {
     }
 
     /// Permissionless crank: auto-approve after review deadline (creator didn't respond)
-    pub fn auto_approve(ctx: Context<AutoApprove>) -> Result<()> {
+    pub fn auto_approve<'info>(ctx: Context<'_, '_, '_, 'info, AutoApprove<'info>>) -> Result<()> {
         let quest = &mut ctx.accounts.quest;
         let claim = &mut ctx.accounts.claim;
         let config = &ctx.accounts.config;
@@ -478,370 +1234,3264 @@ pub mod quest {
         let review_deadline = claim.review_deadline.ok_or(QuestError::NoReviewDeadline)?;
         require!(now > review_deadline, QuestError::DeadlineNotReached);
 
-        let fee_amount = quest.reward_amount
-            .checked_mul(config.fee_basis_points as u64)
-            .ok_or(QuestError::Overflow)?
-            .checked_div(10000)
-            .ok_or(QuestError::Overflow)?;
-        let reward_after_fee = quest.reward_amount
-            .checked_sub(fee_amount)
-            .ok_or(QuestError::Overflow)?;
+        let quest_creator = quest.creator;
 
         let quest_seeds = &[
-            b"quest",
+            b"quest".as_ref(),
+            quest_creator.as_ref(),
             &quest.id.to_le_bytes(),
             &[quest.bump],
         ];
         let signer_seeds = &[&quest_seeds[..]];
+        let payout = EscrowPayout {
+            quest: quest.to_account_info(),
+            signer_seeds,
+            escrow: &ctx.accounts.escrow,
+            reward_mint: &ctx.accounts.reward_mint,
+            unwrap_account: &ctx.accounts.unwrap_account,
+            unwrap_bump: ctx.bumps.unwrap_account,
+            payer: ctx.accounts.cranker.to_account_info(),
+            token_program: &ctx.accounts.token_program,
+            system_program: &ctx.accounts.system_program,
+        };
+        let parties = SettlementParties {
+            creator: ctx.accounts.creator.to_account_info(),
+            creator_token_account: ctx.accounts.creator_token_account.as_ref(),
+            claimer: ctx.accounts.claimer.to_account_info(),
+            claimer_token_account: ctx.accounts.claimer_token_account.as_ref(),
+            members: ctx.remaining_accounts,
+        };
+
+        approve_claim(
+            config,
+            quest,
+            claim,
+            &payout,
+            &parties,
+            ctx.accounts.treasury.to_account_info(),
+            &mut SettlementStats {
+                creator: &mut ctx.accounts.creator_stats,
+                claimer: &mut ctx.accounts.claimer_stats,
+                profile: &mut ctx.accounts.profile,
+            },
+            FULL_AWARD_BPS,
+        )
+    }
 
-        let claimer_payout = reward_after_fee
-            .checked_add(claim.stake_amount)
-            .ok_or(QuestError::Overflow)?;
+    /// Creator-only: approve directly, without waiting on the oracle
+    pub fn creator_approve<'info>(ctx: Context<'_, '_, '_, 'info, CreatorApprove<'info>>) -> Result<()> {
+        let quest = &mut ctx.accounts.quest;
+        let claim = &mut ctx.accounts.claim;
+        let config = &ctx.accounts.config;
+        let now = Clock::get()?.unix_timestamp;
 
-        token::transfer(
-            CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                Transfer {
-                    from: ctx.accounts.escrow.to_account_info(),
-                    to: ctx.accounts.claimer_token_account.to_account_info(),
-                    authority: quest.to_account_info(),
-                },
-                signer_seeds,
-            ),
-            claimer_payout,
-        )?;
+        require!(claim.status == ClaimStatus::Submitted, QuestError::ClaimNotSubmitted);
+        require!(quest.creator == ctx.accounts.creator.key(), QuestError::NotCreator);
+        let review_deadline = claim.review_deadline.ok_or(QuestError::NoReviewDeadline)?;
+        require!(now <= review_deadline, QuestError::ReviewDeadlinePassed);
 
-        if fee_amount > 0 {
-            token::transfer(
-                CpiContext::new_with_signer(
-                    ctx.accounts.token_program.to_account_info(),
-                    Transfer {
-                        from: ctx.accounts.escrow.to_account_info(),
-                        to: ctx.accounts.treasury.to_account_info(),
-                        authority: quest.to_account_info(),
-                    },
-                    signer_seeds,
-                ),
-                fee_amount,
+        let quest_creator = quest.creator;
+
+        let quest_seeds = &[
+            b"quest".as_ref(),
+            quest_creator.as_ref(),
+            &quest.id.to_le_bytes(),
+            &[quest.bump],
+        ];
+        let signer_seeds = &[&quest_seeds[..]];
+        let payout = EscrowPayout {
+            quest: quest.to_account_info(),
+            signer_seeds,
+            escrow: &ctx.accounts.escrow,
+            reward_mint: &ctx.accounts.reward_mint,
+            unwrap_account: &ctx.accounts.unwrap_account,
+            unwrap_bump: ctx.bumps.unwrap_account,
+            payer: ctx.accounts.creator.to_account_info(),
+            token_program: &ctx.accounts.token_program,
+            system_program: &ctx.accounts.system_program,
+        };
+        let parties = SettlementParties {
+            creator: ctx.accounts.creator.to_account_info(),
+            creator_token_account: ctx.accounts.creator_token_account.as_ref(),
+            claimer: ctx.accounts.claimer.to_account_info(),
+            claimer_token_account: ctx.accounts.claimer_token_account.as_ref(),
+            members: ctx.remaining_accounts,
+        };
+
+        approve_claim(
+            config,
+            quest,
+            claim,
+            &payout,
+            &parties,
+            ctx.accounts.treasury.to_account_info(),
+            &mut SettlementStats {
+                creator: &mut ctx.accounts.creator_stats,
+                claimer: &mut ctx.accounts.claimer_stats,
+                profile: &mut ctx.accounts.profile,
+            },
+            FULL_AWARD_BPS,
+        )
+    }
+
+    /// Creator-only: reject directly; the claimer can still dispute
+    pub fn creator_reject(ctx: Context<CreatorReject>) -> Result<()> {
+        let quest = &ctx.accounts.quest;
+        let claim = &mut ctx.accounts.claim;
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(claim.status == ClaimStatus::Submitted, QuestError::ClaimNotSubmitted);
+        require!(quest.creator == ctx.accounts.creator.key(), QuestError::NotCreator);
+        let review_deadline = claim.review_deadline.ok_or(QuestError::NoReviewDeadline)?;
+        require!(now <= review_deadline, QuestError::ReviewDeadlinePassed);
+
+        mark_rejected(quest, claim, false, &ctx.accounts.config.dispute)
+    }
+
+    /// Oracle vote or creator call: send a submitted proof back to the claimer
+    /// with a fresh proof window instead of settling it. The creator acts alone
+    /// within the review window; oracles need the set's threshold.
+    pub fn request_revision(ctx: Context<RequestRevision>) -> Result<()> {
+        let quest = &ctx.accounts.quest;
+        let claim = &mut ctx.accounts.claim;
+        let reviewer = ctx.accounts.reviewer.key();
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(claim.status == ClaimStatus::Submitted, QuestError::ClaimNotSubmitted);
+        require!(claim.revisions < MAX_REVISIONS, QuestError::RevisionLimitReached);
+        // Sending a proof back only makes sense with time left to redo it
+        let proof_deadline = quest
+            .fresh_proof_deadline(now, &ctx.accounts.config.deadline_bounds)?
+            .ok_or(QuestError::NoProofWindowLeft)?;
+
+        if reviewer == quest.creator {
+            let review_deadline = claim.review_deadline.ok_or(QuestError::NoReviewDeadline)?;
+            require!(now <= review_deadline, QuestError::ReviewDeadlinePassed);
+        } else {
+            let quorum = record_oracle_vote(
+                &ctx.accounts.oracle_set,
+                quest,
+                claim,
+                reviewer,
+                OracleVerdict::Revise,
             )?;
+            if !quorum {
+                return Ok(());
+            }
         }
 
-        claim.status = ClaimStatus::Approved;
-        quest.status = QuestStatus::Completed;
+        claim.revisions += 1;
+        claim.status = ClaimStatus::NeedsRevision;
+        let profile = &mut ctx.accounts.profile;
+        profile.active_claims = profile.active_claims
+            .checked_add(1)
+            .ok_or(QuestError::Overflow)?;
+        claim.proof_deadline = proof_deadline;
+        claim.review_deadline = None;
+        claim.oracle_votes.clear();
+        claim.proof_hash = None;
+        claim.submitted_at = None;
 
-        emit!(QuestCompleted {
+        emit!(RevisionRequested {
             quest_id: quest.id,
+            quest: quest.key(),
             claimer: claim.claimer,
-            reward_amount: reward_after_fee,
-            fee_amount,
+            step: claim.current_step,
+            revision: claim.revisions,
+            proof_deadline: claim.proof_deadline,
         });
 
         Ok(())
     }
 
-    pub fn cancel_quest(ctx: Context<CancelQuest>) -> Result<()> {
+    /// Permissionless crank: settle a rejection nobody disputed
+    pub fn finalize_rejection<'info>(ctx: Context<'_, '_, '_, 'info, FinalizeRejection<'info>>) -> Result<()> {
         let quest = &mut ctx.accounts.quest;
+        let claim = &mut ctx.accounts.claim;
+        let now = Clock::get()?.unix_timestamp;
 
-        require!(quest.status == QuestStatus::Active, QuestError::QuestNotActive);
-        require!(quest.current_claimers == 0, QuestError::QuestAlreadyClaimed);
-        require!(quest.creator == ctx.accounts.creator.key(), QuestError::NotCreator);
+        require!(
+            claim.status == ClaimStatus::RejectionPending,
+            QuestError::NoPendingRejection
+        );
+        let dispute_deadline = claim.dispute_deadline.ok_or(QuestError::NoPendingRejection)?;
+        require!(now > dispute_deadline, QuestError::DeadlineNotReached);
+
+        let quest_creator = quest.creator;
 
         let quest_seeds = &[
-            b"quest",
+            b"quest".as_ref(),
+            quest_creator.as_ref(),
             &quest.id.to_le_bytes(),
             &[quest.bump],
         ];
         let signer_seeds = &[&quest_seeds[..]];
+        let payout = EscrowPayout {
+            quest: quest.to_account_info(),
+            signer_seeds,
+            escrow: &ctx.accounts.escrow,
+            reward_mint: &ctx.accounts.reward_mint,
+            unwrap_account: &ctx.accounts.unwrap_account,
+            unwrap_bump: ctx.bumps.unwrap_account,
+            payer: ctx.accounts.cranker.to_account_info(),
+            token_program: &ctx.accounts.token_program,
+            system_program: &ctx.accounts.system_program,
+        };
+        let parties = SettlementParties {
+            creator: ctx.accounts.creator.to_account_info(),
+            creator_token_account: ctx.accounts.creator_token_account.as_ref(),
+            claimer: ctx.accounts.claimer.to_account_info(),
+            claimer_token_account: ctx.accounts.claimer_token_account.as_ref(),
+            members: ctx.remaining_accounts,
+        };
+
+        let safety_flagged = claim.safety_flagged;
+        if safety_flagged {
+            ctx.accounts.profile.add_strike();
+        }
+        reject_claim(
+            &ctx.accounts.config,
+            quest,
+            claim,
+            &payout,
+            &parties,
+            ctx.accounts.treasury.to_account_info(),
+            &mut ctx.accounts.claimer_stats,
+            safety_flagged,
+        )
+    }
 
-        token::transfer(
-            CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                Transfer {
-                    from: ctx.accounts.escrow.to_account_info(),
-                    to: ctx.accounts.creator_token_account.to_account_info(),
-                    authority: quest.to_account_info(),
-                },
-                signer_seeds,
-            ),
-            quest.reward_amount,
-        )?;
+    /// Claimer contests a rejection by posting a bond; escrow stays frozen
+    /// until the jurors have voted
+    pub fn open_dispute(ctx: Context<OpenDispute>) -> Result<()> {
+        let claim = &mut ctx.accounts.claim;
+        let config = &ctx.accounts.config;
+        let now = Clock::get()?.unix_timestamp;
 
-        quest.status = QuestStatus::Cancelled;
+        require!(
+            claim.status == ClaimStatus::RejectionPending,
+            QuestError::NoPendingRejection
+        );
+        let dispute_deadline = claim.dispute_deadline.ok_or(QuestError::NoPendingRejection)?;
+        require!(now <= dispute_deadline, QuestError::DisputeWindowClosed);
+
+        let bond = config.dispute.bond_lamports;
+        if bond > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.claimer.to_account_info(),
+                        to: ctx.accounts.dispute.to_account_info(),
+                    },
+                ),
+                bond,
+            )?;
+        }
 
-        emit!(QuestCancelled {
-            quest_id: quest.id,
-            creator: quest.creator,
+        let dispute = &mut ctx.accounts.dispute;
+        dispute.claim = claim.key();
+        dispute.claimer = claim.claimer;
+        dispute.bond = bond;
+        dispute.voting_ends_at = now
+            .checked_add(config.dispute.voting_seconds)
+            .ok_or(QuestError::Overflow)?;
+        dispute.votes_for_claimer = 0;
+        dispute.votes_for_creator = 0;
+        dispute.votes = Vec::new();
+        dispute.bump = ctx.bumps.dispute;
+
+        claim.status = ClaimStatus::Disputed;
+
+        emit!(DisputeOpened {
+            quest_id: ctx.accounts.quest.id,
+            quest: ctx.accounts.quest.key(),
+            claimer: claim.claimer,
+            bond,
+            voting_ends_at: dispute.voting_ends_at,
         });
 
         Ok(())
     }
-}
 
-// ============================================================================
-// ACCOUNTS
-// ============================================================================
+    /// Juror-only: vote on an open dispute
+    pub fn cast_vote(ctx: Context<CastVote>, for_claimer: bool) -> Result<()> {
+        let dispute = &mut ctx.accounts.dispute;
+        let juror = ctx.accounts.juror.key();
+        let now = Clock::get()?.unix_timestamp;
 
-#[derive(Accounts)]
-pub struct Initialize<'info> {
-    #[account(
-        init,
-        payer = authority,
-        space = 8 + QuestConfig::INIT_SPACE,
-        seeds = [b"config"],
-        bump
-    )]
-    pub config: Account<'info, QuestConfig>,
+        require!(
+            ctx.accounts.juror_set.jurors.contains(&juror),
+            QuestError::NotJuror
+        );
+        require!(now <= dispute.voting_ends_at, QuestError::VotingClosed);
+        require!(
+            !dispute.votes.iter().any(|v| v.juror == juror),
+            QuestError::AlreadyVoted
+        );
 
-    /// CHECK: Treasury account for fees
-    pub treasury: UncheckedAccount<'info>,
+        dispute.votes.push(JurorVote { juror, for_claimer });
+        if for_claimer {
+            dispute.votes_for_claimer = dispute.votes_for_claimer
+                .checked_add(1)
+                .ok_or(QuestError::Overflow)?;
+        } else {
+            dispute.votes_for_creator = dispute.votes_for_creator
+                .checked_add(1)
+                .ok_or(QuestError::Overflow)?;
+        }
 
-    #[account(mut)]
-    pub authority: Signer<'info>,
+        emit!(VoteCast {
+            claim: dispute.claim,
+            juror,
+            for_claimer,
+        });
 
-    pub system_program: Program<'info, System>,
-}
+        Ok(())
+    }
 
-#[derive(Accounts)]
-pub struct CreateQuest<'info> {
-    #[account(mut, seeds = [b"config"], bump = config.bump)]
-    pub config: Account<'info, QuestConfig>,
+    /// Permissionless crank: settle a dispute by majority once voting ends.
+    /// The claimer wins only on a strict majority; otherwise the rejection stands.
+    /// A losing bond is split between the jurors who sided with the creator,
+    /// whose wallets are passed as remaining accounts in voting order. On a tie
+    /// the bond goes back to the claimer.
+    pub fn resolve_dispute<'info>(ctx: Context<'_, '_, '_, 'info, ResolveDispute<'info>>) -> Result<()> {
+        let quest = &mut ctx.accounts.quest;
+        let claim = &mut ctx.accounts.claim;
+        let config = &ctx.accounts.config;
+        let now = Clock::get()?.unix_timestamp;
 
-    #[account(
-        init,
-        payer = creator,
-        space = 8 + Quest::INIT_SPACE,
-        seeds = [b"quest", &config.quest_count.to_le_bytes()],
-        bump
-    )]
-    pub quest: Account<'info, Quest>,
+        require!(claim.status == ClaimStatus::Disputed, QuestError::ClaimNotDisputed);
+        let dispute = &ctx.accounts.dispute;
+        require!(now > dispute.voting_ends_at, QuestError::DeadlineNotReached);
+
+        let claimer_wins = dispute.votes_for_claimer > dispute.votes_for_creator;
+        let bond_forfeited = dispute.votes_for_creator > dispute.votes_for_claimer;
+
+        // Pay the majority jurors straight out of the dispute account; whatever is
+        // left (rent, rounding dust, or the whole bond) returns to the claimer on close.
+        // Their wallets lead the remaining accounts; any guild members follow.
+        let mut juror_accounts = 0;
+        if bond_forfeited && dispute.bond > 0 {
+            let winners: Vec<Pubkey> = dispute.votes
+                .iter()
+                .filter(|v| !v.for_claimer)
+                .map(|v| v.juror)
+                .collect();
+            require!(
+                ctx.remaining_accounts.len() >= winners.len(),
+                QuestError::JurorAccountsMismatch
+            );
+            juror_accounts = winners.len();
 
-    #[account(
-        init,
-        payer = creator,
-        token::mint = reward_mint,
-        token::authority = quest,
-        seeds = [b"escrow", quest.key().as_ref()],
-        bump
-    )]
-    pub escrow: Account<'info, TokenAccount>,
+            let share = dispute.bond
+                .checked_div(winners.len() as u64)
+                .ok_or(QuestError::Overflow)?;
+            let dispute_info = dispute.to_account_info();
+            for (juror, wallet) in winners.iter().zip(ctx.remaining_accounts.iter()) {
+                require!(wallet.key() == *juror, QuestError::JurorAccountsMismatch);
+
+                let mut from = dispute_info.try_borrow_mut_lamports()?;
+                **from = from.checked_sub(share).ok_or(QuestError::Overflow)?;
+                let mut to = wallet.try_borrow_mut_lamports()?;
+                **to = to.checked_add(share).ok_or(QuestError::Overflow)?;
+            }
+        }
 
-    pub reward_mint: Account<'info, token::Mint>,
+        emit!(DisputeResolved {
+            quest_id: quest.id,
+            quest: quest.key(),
+            claimer: claim.claimer,
+            claimer_wins,
+            votes_for_claimer: dispute.votes_for_claimer,
+            votes_for_creator: dispute.votes_for_creator,
+        });
 
-    #[account(mut)]
-    pub creator: Signer<'info>,
+        let quest_creator = quest.creator;
 
-    #[account(mut, constraint = creator_token_account.owner == creator.key())]
-    pub creator_token_account: Account<'info, TokenAccount>,
+        let quest_seeds = &[
+            b"quest".as_ref(),
+            quest_creator.as_ref(),
+            &quest.id.to_le_bytes(),
+            &[quest.bump],
+        ];
+        let signer_seeds = &[&quest_seeds[..]];
+        let payout = EscrowPayout {
+            quest: quest.to_account_info(),
+            signer_seeds,
+            escrow: &ctx.accounts.escrow,
+            reward_mint: &ctx.accounts.reward_mint,
+            unwrap_account: &ctx.accounts.unwrap_account,
+            unwrap_bump: ctx.bumps.unwrap_account,
+            payer: ctx.accounts.cranker.to_account_info(),
+            token_program: &ctx.accounts.token_program,
+            system_program: &ctx.accounts.system_program,
+        };
+        let parties = SettlementParties {
+            creator: ctx.accounts.creator.to_account_info(),
+            creator_token_account: ctx.accounts.creator_token_account.as_ref(),
+            claimer: ctx.accounts.claimer.to_account_info(),
+            claimer_token_account: ctx.accounts.claimer_token_account.as_ref(),
+            members: &ctx.remaining_accounts[juror_accounts..],
+        };
+
+        if claimer_wins {
+            approve_claim(
+                config,
+                quest,
+                claim,
+                &payout,
+                &parties,
+                ctx.accounts.treasury.to_account_info(),
+                &mut SettlementStats {
+                    creator: &mut ctx.accounts.creator_stats,
+                    claimer: &mut ctx.accounts.claimer_stats,
+                    profile: &mut ctx.accounts.profile,
+                },
+                FULL_AWARD_BPS,
+            )
+        } else {
+            let safety_flagged = claim.safety_flagged;
+            if safety_flagged {
+                ctx.accounts.profile.add_strike();
+            }
+            reject_claim(
+                config,
+                quest,
+                claim,
+                &payout,
+                &parties,
+                ctx.accounts.treasury.to_account_info(),
+                &mut ctx.accounts.claimer_stats,
+                safety_flagged,
+            )
+        }
+    }
 
-    pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
-}
+    pub fn cancel_quest(ctx: Context<CancelQuest>) -> Result<()> {
+        let quest = &mut ctx.accounts.quest;
 
-#[derive(Accounts)]
-pub struct ClaimQuest<'info> {
-    #[account(mut)]
-    pub quest: Account<'info, Quest>,
+        require!(quest.status == QuestStatus::Active, QuestError::QuestNotActive);
+        require!(quest.current_claimers == 0, QuestError::QuestAlreadyClaimed);
+        require!(quest.creator == ctx.accounts.creator.key(), QuestError::NotCreator);
 
-    #[account(
-        init,
-        payer = claimer,
-        space = 8 + Claim::INIT_SPACE,
-        seeds = [b"claim", quest.key().as_ref(), claimer.key().as_ref()],
-        bump
-    )]
-    pub claim: Account<'info, Claim>,
+        quest.status = QuestStatus::Cancelled;
 
-    #[account(mut, seeds = [b"escrow", quest.key().as_ref()], bump)]
-    pub escrow: Account<'info, TokenAccount>,
+        emit!(QuestCancelled {
+            quest_id: quest.id,
+            quest: quest.key(),
+            creator: quest.creator,
+        });
 
-    #[account(mut)]
-    pub claimer: Signer<'info>,
+        let quest_creator = quest.creator;
 
-    #[account(mut, constraint = claimer_token_account.owner == claimer.key())]
-    pub claimer_token_account: Account<'info, TokenAccount>,
+        let quest_seeds = &[
+            b"quest".as_ref(),
+            quest_creator.as_ref(),
+            &quest.id.to_le_bytes(),
+            &[quest.bump],
+        ];
+        let signer_seeds = &[&quest_seeds[..]];
+        let payout = EscrowPayout {
+            quest: quest.to_account_info(),
+            signer_seeds,
+            escrow: &ctx.accounts.escrow,
+            reward_mint: &ctx.accounts.reward_mint,
+            unwrap_account: &ctx.accounts.unwrap_account,
+            unwrap_bump: ctx.bumps.unwrap_account,
+            payer: ctx.accounts.creator.to_account_info(),
+            token_program: &ctx.accounts.token_program,
+            system_program: &ctx.accounts.system_program,
+        };
+
+        // No live claims, so everything left in escrow is unpaid reward (for pooled
+        // quests, the slots that were never paid out)
+        close_quest_accounts(
+            quest,
+            &payout,
+            ctx.accounts.creator.to_account_info(),
+            ctx.accounts.creator_token_account.as_ref(),
+            ctx.accounts.quest_description.as_ref(),
+        )
+    }
 
-    pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
-}
+    /// Permissionless crank: expire a quest once its time limit passes. Reward that
+    /// no live claim can still earn goes back to the creator; live claims run to
+    /// their own deadlines and the last one to settle closes the quest.
+    pub fn expire_quest(ctx: Context<ExpireQuest>) -> Result<()> {
+        let quest = &mut ctx.accounts.quest;
+        let now = Clock::get()?.unix_timestamp;
 
-#[derive(Accounts)]
-pub struct SubmitProof<'info> {
-    pub quest: Account<'info, Quest>,
+        require!(
+            matches!(quest.status, QuestStatus::Active | QuestStatus::Claimed),
+            QuestError::QuestNotActive
+        );
+        let time_limit = quest.time_limit.ok_or(QuestError::NoTimeLimit)?;
+        require!(now > time_limit, QuestError::DeadlineNotReached);
 
-    #[account(
-        mut,
-        seeds = [b"claim", quest.key().as_ref(), claimer.key().as_ref()],
-        bump = claim.bump
-    )]
-    pub claim: Account<'info, Claim>,
+        quest.status = QuestStatus::Expired;
 
-    pub claimer: Signer<'info>,
+        let quest_creator = quest.creator;
+
+        let quest_seeds = &[
+            b"quest".as_ref(),
+            quest_creator.as_ref(),
+            &quest.id.to_le_bytes(),
+            &[quest.bump],
+        ];
+        let signer_seeds = &[&quest_seeds[..]];
+        let payout = EscrowPayout {
+            quest: quest.to_account_info(),
+            signer_seeds,
+            escrow: &ctx.accounts.escrow,
+            reward_mint: &ctx.accounts.reward_mint,
+            unwrap_account: &ctx.accounts.unwrap_account,
+            unwrap_bump: ctx.bumps.unwrap_account,
+            payer: ctx.accounts.cranker.to_account_info(),
+            token_program: &ctx.accounts.token_program,
+            system_program: &ctx.accounts.system_program,
+        };
+
+        if quest.current_claimers == 0 {
+            let refunded = ctx.accounts.escrow.amount;
+
+            emit!(QuestExpired {
+                quest_id: quest.id,
+                quest: quest.key(),
+                refunded,
+                live_claims: 0,
+            });
+
+            // Nothing is staked, so the whole escrow is unpaid reward
+            return close_quest_accounts(
+                quest,
+                &payout,
+                ctx.accounts.creator.to_account_info(),
+                ctx.accounts.creator_token_account.as_ref(),
+                None,
+            );
+        }
+
+        // Only pooled quests have reward beyond what the live claims can earn:
+        // the slots that were never taken
+        let refunded = if quest.pooled {
+            let open_slots = quest.max_claimers
+                .saturating_sub(quest.completed_claimers)
+                .saturating_sub(quest.current_claimers);
+            quest.reward_amount
+                .checked_mul(open_slots as u64)
+                .ok_or(QuestError::Overflow)?
+        } else {
+            0
+        };
+        let creator_refund = reserve_contributor_refund(quest, refunded)?;
+        if creator_refund > 0 {
+            payout.pay(
+                ctx.accounts.creator_token_account.as_ref(),
+                &ctx.accounts.creator,
+                creator_refund,
+            )?;
+        }
+
+        emit!(QuestExpired {
+            quest_id: quest.id,
+            quest: quest.key(),
+            refunded,
+            live_claims: quest.current_claimers,
+        });
+
+        Ok(())
+    }
+
+    /// Anyone can top up an active quest's reward. Refunded pro rata alongside
+    /// the creator if the reward goes back (cancel, expiry, rejection).
+    pub fn add_to_reward(ctx: Context<AddToReward>, amount: u64) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let quest = &ctx.accounts.quest;
+
+        require!(quest.status == QuestStatus::Active, QuestError::QuestNotActive);
+        if let Some(limit) = quest.time_limit {
+            require!(now < limit, QuestError::QuestExpired);
+        }
+        require!(amount > 0, QuestError::ContributionTooLow);
+        // Creator money is creator_funded; counting it as a contribution would
+        // shift the refund split toward the contributor pool
+        require!(
+            ctx.accounts.contributor.key() != quest.creator,
+            QuestError::CreatorCannotContribute
+        );
+
+        let received = fund_escrow(
+            &ctx.accounts.token_program,
+            &ctx.accounts.system_program,
+            &mut ctx.accounts.escrow,
+            &ctx.accounts.reward_mint,
+            &ctx.accounts.contributor,
+            ctx.accounts.contributor_token_account.as_ref(),
+            amount,
+        )?;
+
+        let quest = &mut ctx.accounts.quest;
+        // Pooled top-ups are spread over the slots not yet paid out; the
+        // remainder that doesn't divide evenly is swept with the escrow
+        let increase = if quest.pooled {
+            let open_slots = quest.max_claimers.saturating_sub(quest.completed_claimers);
+            received
+                .checked_div(open_slots as u64)
+                .ok_or(QuestError::Overflow)?
+        } else {
+            received
+        };
+        require!(increase > 0, QuestError::ContributionTooLow);
+        quest.reward_amount = quest.reward_amount
+            .checked_add(increase)
+            .ok_or(QuestError::Overflow)?;
+        quest.contributed_amount = quest.contributed_amount
+            .checked_add(received)
+            .ok_or(QuestError::Overflow)?;
+
+        let contribution = &mut ctx.accounts.contribution;
+        if contribution.amount == 0 {
+            contribution.quest = quest.key();
+            contribution.contributor = ctx.accounts.contributor.key();
+            contribution.bump = ctx.bumps.contribution;
+            quest.contributors = quest.contributors
+                .checked_add(1)
+                .ok_or(QuestError::Overflow)?;
+        }
+        contribution.amount = contribution.amount
+            .checked_add(received)
+            .ok_or(QuestError::Overflow)?;
+
+        emit!(RewardIncreased {
+            quest_id: quest.id,
+            quest: quest.key(),
+            contributor: contribution.contributor,
+            amount: received,
+            reward_amount: quest.reward_amount,
+        });
+
+        Ok(())
+    }
+
+    /// Permissionless crank: pay a contributor their share of the returned reward
+    /// (zero if it was paid out) and close their contribution. The last refund
+    /// closes the quest.
+    pub fn refund_contribution(ctx: Context<RefundContribution>) -> Result<()> {
+        let quest = &mut ctx.accounts.quest;
+        let contribution = &ctx.accounts.contribution;
+
+        require!(quest.refunds_ready, QuestError::QuestNotSettled);
+
+        let refund = (quest.contributor_refund_pool as u128)
+            .checked_mul(contribution.amount as u128)
+            .ok_or(QuestError::Overflow)?
+            .checked_div(quest.contributed_amount as u128)
+            .ok_or(QuestError::Overflow)? as u64;
+        quest.contributor_refund_pool = quest.contributor_refund_pool
+            .checked_sub(refund)
+            .ok_or(QuestError::Overflow)?;
+        quest.contributed_amount = quest.contributed_amount
+            .checked_sub(contribution.amount)
+            .ok_or(QuestError::Overflow)?;
+        quest.contributors = quest.contributors.saturating_sub(1);
+
+        let quest_creator = quest.creator;
+
+        let quest_seeds = &[
+            b"quest".as_ref(),
+            quest_creator.as_ref(),
+            &quest.id.to_le_bytes(),
+            &[quest.bump],
+        ];
+        let signer_seeds = &[&quest_seeds[..]];
+        let payout = EscrowPayout {
+            quest: quest.to_account_info(),
+            signer_seeds,
+            escrow: &ctx.accounts.escrow,
+            reward_mint: &ctx.accounts.reward_mint,
+            unwrap_account: &ctx.accounts.unwrap_account,
+            unwrap_bump: ctx.bumps.unwrap_account,
+            payer: ctx.accounts.cranker.to_account_info(),
+            token_program: &ctx.accounts.token_program,
+            system_program: &ctx.accounts.system_program,
+        };
+
+        if refund > 0 {
+            payout.pay(
+                ctx.accounts.contributor_token_account.as_ref(),
+                &ctx.accounts.contributor,
+                refund,
+            )?;
+        }
+
+        emit!(ContributionRefunded {
+            quest_id: quest.id,
+            quest: quest.key(),
+            contributor: contribution.contributor,
+            amount: refund,
+        });
+
+        if quest.contributors == 0 {
+            close_quest_accounts(
+                quest,
+                &payout,
+                ctx.accounts.creator.to_account_info(),
+                ctx.accounts.creator_token_account.as_ref(),
+                None,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Permissionless crank: return a settled claim's rent to the claimer
+    pub fn close_claim(ctx: Context<CloseClaim>) -> Result<()> {
+        require!(ctx.accounts.claim.is_settled(), QuestError::ClaimNotSettled);
+
+        // While the quest is still open the claimer could re-create the claim PDA
+        // and take another slot, so wait until it is settled or already closed
+        let quest_info = ctx.accounts.quest.to_account_info();
+        if quest_info.owner == &crate::ID {
+            let data = quest_info.try_borrow_data()?;
+            let quest = Quest::try_deserialize(&mut &data[..])?;
+            require!(quest.is_settled(), QuestError::QuestNotSettled);
+        }
+
+        Ok(())
+    }
+
+    /// Permissionless crank: close a proof record once its claim is settled or
+    /// closed, returning rent to the claimer
+    pub fn close_proof(ctx: Context<CloseProof>) -> Result<()> {
+        let claim_info = ctx.accounts.claim.to_account_info();
+        if claim_info.owner == &crate::ID {
+            let data = claim_info.try_borrow_data()?;
+            let claim = Claim::try_deserialize(&mut &data[..])?;
+            require!(claim.is_settled(), QuestError::ClaimNotSettled);
+        }
+
+        Ok(())
+    }
+
+    /// Permissionless crank: close a settled quest once no stakes are left in
+    /// escrow. Leftover dust goes back to the creator along with the rent.
+    pub fn close_quest(ctx: Context<CloseQuest>) -> Result<()> {
+        let quest = &mut ctx.accounts.quest;
+
+        require!(quest.is_settled(), QuestError::QuestNotSettled);
+        require!(quest.current_claimers == 0, QuestError::LiveClaimsRemaining);
+
+        let quest_creator = quest.creator;
+
+        let quest_seeds = &[
+            b"quest".as_ref(),
+            quest_creator.as_ref(),
+            &quest.id.to_le_bytes(),
+            &[quest.bump],
+        ];
+        let signer_seeds = &[&quest_seeds[..]];
+        let payout = EscrowPayout {
+            quest: quest.to_account_info(),
+            signer_seeds,
+            escrow: &ctx.accounts.escrow,
+            reward_mint: &ctx.accounts.reward_mint,
+            unwrap_account: &ctx.accounts.unwrap_account,
+            unwrap_bump: ctx.bumps.unwrap_account,
+            payer: ctx.accounts.cranker.to_account_info(),
+            token_program: &ctx.accounts.token_program,
+            system_program: &ctx.accounts.system_program,
+        };
+
+        close_quest_accounts(
+            quest,
+            &payout,
+            ctx.accounts.creator.to_account_info(),
+            ctx.accounts.creator_token_account.as_ref(),
+            ctx.accounts.quest_description.as_ref(),
+        )
+    }
+
+    /// Permissionless crank: close a stored description once its quest is
+    /// settled or closed, returning rent to the creator
+    pub fn close_description(ctx: Context<CloseDescription>) -> Result<()> {
+        let quest_info = ctx.accounts.quest.to_account_info();
+        if quest_info.owner == &crate::ID {
+            let data = quest_info.try_borrow_data()?;
+            let quest = Quest::try_deserialize(&mut &data[..])?;
+            require!(quest.is_settled(), QuestError::QuestNotSettled);
+        }
+
+        Ok(())
+    }
 }
 
+// ============================================================================
+// ACCOUNTS
+// ============================================================================
+
 #[derive(Accounts)]
-pub struct ApproveCompletion<'info> {
-    #[account(seeds = [b"config"], bump = config.bump)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + QuestConfig::INIT_SPACE,
+        seeds = [b"config"],
+        bump
+    )]
     pub config: Account<'info, QuestConfig>,
 
-    #[account(mut)]
-    pub quest: Account<'info, Quest>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + JurorSet::INIT_SPACE,
+        seeds = [b"jurors"],
+        bump
+    )]
+    pub juror_set: Account<'info, JurorSet>,
 
-    #[account(mut)]
-    pub claim: Account<'info, Claim>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + OracleSet::INIT_SPACE,
+        seeds = [b"oracles"],
+        bump
+    )]
+    pub oracle_set: Account<'info, OracleSet>,
 
-    #[account(mut, seeds = [b"escrow", quest.key().as_ref()], bump)]
-    pub escrow: Account<'info, TokenAccount>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + GlobalStats::INIT_SPACE,
+        seeds = [b"global_stats"],
+        bump
+    )]
+    pub global_stats: Account<'info, GlobalStats>,
 
-    #[account(mut)]
-    pub claimer_token_account: Account<'info, TokenAccount>,
+    /// CHECK: Treasury owner wallet; fees go to its token account for each reward mint
+    pub treasury: UncheckedAccount<'info>,
+
+    /// CHECK: First member of the oracle set; kept separate from the admin authority
+    pub oracle: UncheckedAccount<'info>,
 
     #[account(mut)]
-    pub treasury: Account<'info, TokenAccount>,
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, QuestConfig>,
 
     pub authority: Signer<'info>,
-    pub token_program: Program<'info, Token>,
 }
 
 #[derive(Accounts)]
-pub struct RejectCompletion<'info> {
-    #[account(seeds = [b"config"], bump = config.bump)]
+pub struct AcceptAuthority<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
     pub config: Account<'info, QuestConfig>,
 
-    #[account(mut)]
-    pub quest: Account<'info, Quest>,
+    pub new_authority: Signer<'info>,
+}
 
-    #[account(mut)]
-    pub claim: Account<'info, Claim>,
+#[derive(Accounts)]
+pub struct ManageOracles<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, QuestConfig>,
 
-    #[account(mut, seeds = [b"escrow", quest.key().as_ref()], bump)]
-    pub escrow: Account<'info, TokenAccount>,
+    #[account(mut, seeds = [b"oracles"], bump = oracle_set.bump)]
+    pub oracle_set: Account<'info, OracleSet>,
 
-    #[account(mut)]
-    pub creator_token_account: Account<'info, TokenAccount>,
+    pub authority: Signer<'info>,
+}
 
-    #[account(mut)]
-    pub claimer_token_account: Account<'info, TokenAccount>,
+#[derive(Accounts)]
+pub struct ManageJurors<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, QuestConfig>,
+
+    #[account(mut, seeds = [b"jurors"], bump = juror_set.bump)]
+    pub juror_set: Account<'info, JurorSet>,
 
     pub authority: Signer<'info>,
-    pub token_program: Program<'info, Token>,
 }
 
 #[derive(Accounts)]
-pub struct AbandonClaim<'info> {
-    #[account(mut)]
-    pub quest: Account<'info, Quest>,
+#[instruction(wallet: Pubkey)]
+pub struct ManageProfile<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, QuestConfig>,
 
     #[account(
-        mut,
-        seeds = [b"claim", quest.key().as_ref(), claimer.key().as_ref()],
-        bump = claim.bump
+        init_if_needed,
+        payer = authority,
+        space = 8 + UserProfile::INIT_SPACE,
+        seeds = [b"profile", wallet.as_ref()],
+        bump
     )]
-    pub claim: Account<'info, Claim>,
-
-    #[account(mut, seeds = [b"escrow", quest.key().as_ref()], bump)]
-    pub escrow: Account<'info, TokenAccount>,
+    pub profile: Account<'info, UserProfile>,
 
     #[account(mut)]
-    pub creator_token_account: Account<'info, TokenAccount>,
-
-    pub claimer: Signer<'info>,
-    pub token_program: Program<'info, Token>,
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct ExpireClaim<'info> {
-    #[account(mut)]
-    pub quest: Account<'info, Quest>,
-
-    #[account(mut)]
-    pub claim: Account<'info, Claim>,
-
-    #[account(mut, seeds = [b"escrow", quest.key().as_ref()], bump)]
-    pub escrow: Account<'info, TokenAccount>,
-
-    #[account(mut)]
-    pub creator_token_account: Account<'info, TokenAccount>,
+pub struct ApplyConfigUpdate<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, QuestConfig>,
 
     /// CHECK: Anyone can call this (permissionless crank)
     pub cranker: Signer<'info>,
-    pub token_program: Program<'info, Token>,
 }
 
 #[derive(Accounts)]
-pub struct AutoApprove<'info> {
+pub struct CreateQuest<'info> {
     #[account(seeds = [b"config"], bump = config.bump)]
     pub config: Account<'info, QuestConfig>,
 
-    #[account(mut)]
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = 8 + QuestCounter::INIT_SPACE,
+        seeds = [b"quest_counter", creator.key().as_ref()],
+        bump
+    )]
+    pub quest_counter: Account<'info, QuestCounter>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + Quest::INIT_SPACE,
+        seeds = [
+            b"quest".as_ref(),
+            creator.key().as_ref(),
+            &quest_counter.next_nonce.to_le_bytes(),
+        ],
+        bump
+    )]
     pub quest: Account<'info, Quest>,
 
-    #[account(mut)]
-    pub claim: Account<'info, Claim>,
+    /// Optional: pass to keep the protocol-wide quest count up to date
+    #[account(mut, seeds = [b"global_stats"], bump = global_stats.bump)]
+    pub global_stats: Option<Account<'info, GlobalStats>>,
+
+    #[account(
+        init,
+        payer = creator,
+        token::mint = reward_mint,
+        token::authority = quest,
+        token::token_program = token_program,
+        seeds = [b"escrow", quest.key().as_ref()],
+        bump
+    )]
+    pub escrow: InterfaceAccount<'info, TokenAccount>,
+
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = 8 + UserProfile::INIT_SPACE,
+        seeds = [b"profile", creator.key().as_ref()],
+        bump
+    )]
+    pub profile: Account<'info, UserProfile>,
+
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = 8 + UserStats::INIT_SPACE,
+        seeds = [b"stats", creator.key().as_ref()],
+        bump
+    )]
+    pub creator_stats: Account<'info, UserStats>,
+
+    /// Only passed when the description text is stored on-chain
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + QuestDescription::INIT_SPACE,
+        seeds = [b"description", quest.key().as_ref()],
+        bump
+    )]
+    pub quest_description: Option<Account<'info, QuestDescription>>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    /// Not needed for native SOL rewards, which are wrapped from the creator's lamports
+    #[account(mut, constraint = creator_token_account.owner == creator.key())]
+    pub creator_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimQuest<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, QuestConfig>,
+
+    #[account(mut)]
+    pub quest: Account<'info, Quest>,
+
+    #[account(
+        init,
+        payer = claimer,
+        space = 8 + Claim::INIT_SPACE,
+        seeds = [b"claim", quest.key().as_ref(), claimer.key().as_ref()],
+        bump
+    )]
+    pub claim: Account<'info, Claim>,
+
+    #[account(mut, seeds = [b"escrow", quest.key().as_ref()], bump)]
+    pub escrow: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = quest.reward_mint)]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = claimer,
+        space = 8 + UserProfile::INIT_SPACE,
+        seeds = [b"profile", claimer.key().as_ref()],
+        bump
+    )]
+    pub profile: Account<'info, UserProfile>,
+
+    #[account(
+        init_if_needed,
+        payer = claimer,
+        space = 8 + UserStats::INIT_SPACE,
+        seeds = [b"stats", claimer.key().as_ref()],
+        bump
+    )]
+    pub claimer_stats: Account<'info, UserStats>,
+
+    #[account(mut)]
+    pub claimer: Signer<'info>,
+
+    /// Not needed for native SOL quests, which wrap the stake from the claimer's lamports
+    #[account(mut, constraint = claimer_token_account.owner == claimer.key())]
+    pub claimer_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct JoinGuild<'info> {
+    pub quest: Account<'info, Quest>,
+
+    #[account(mut, constraint = claim.quest == quest.key())]
+    pub claim: Account<'info, Claim>,
+
+    #[account(
+        init_if_needed,
+        payer = member,
+        space = 8 + UserProfile::INIT_SPACE,
+        seeds = [b"profile", member.key().as_ref()],
+        bump
+    )]
+    pub profile: Account<'info, UserProfile>,
+
+    #[account(mut, seeds = [b"escrow", quest.key().as_ref()], bump)]
+    pub escrow: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = quest.reward_mint)]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub member: Signer<'info>,
+
+    /// Not needed for native SOL quests
+    #[account(mut, constraint = member_token_account.owner == member.key())]
+    pub member_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SubmitProof<'info> {
+    pub quest: Account<'info, Quest>,
+
+    #[account(
+        mut,
+        seeds = [b"claim", quest.key().as_ref(), claimer.key().as_ref()],
+        bump = claim.bump
+    )]
+    pub claim: Account<'info, Claim>,
+
+    #[account(mut, seeds = [b"profile", claimer.key().as_ref()], bump = profile.bump)]
+    pub profile: Account<'info, UserProfile>,
+
+    #[account(
+        init_if_needed,
+        payer = claimer,
+        space = 8 + Proof::INIT_SPACE,
+        seeds = [b"proof", claim.key().as_ref()],
+        bump
+    )]
+    pub proof: Account<'info, Proof>,
+
+    #[account(mut)]
+    pub claimer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveCompletion<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, QuestConfig>,
+
+    #[account(seeds = [b"oracles"], bump = oracle_set.bump)]
+    pub oracle_set: Account<'info, OracleSet>,
+
+    #[account(mut)]
+    pub quest: Account<'info, Quest>,
+
+    #[account(mut, constraint = claim.quest == quest.key())]
+    pub claim: Account<'info, Claim>,
+
+    #[account(mut, seeds = [b"stats", quest.creator.as_ref()], bump = creator_stats.bump)]
+    pub creator_stats: Account<'info, UserStats>,
+
+    #[account(mut, seeds = [b"stats", claim.claimer.as_ref()], bump = claimer_stats.bump)]
+    pub claimer_stats: Account<'info, UserStats>,
+
+    #[account(mut, seeds = [b"profile", claim.claimer.as_ref()], bump = profile.bump)]
+    pub profile: Account<'info, UserProfile>,
+
+    #[account(mut, seeds = [b"escrow", quest.key().as_ref()], bump)]
+    pub escrow: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, address = quest.reward_mint)]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: Creator wallet, gets quest and escrow rent back once the quest is settled
+    #[account(mut, address = quest.creator)]
+    pub creator: UncheckedAccount<'info>,
+
+    #[account(mut, constraint = creator_token_account.owner == quest.creator)]
+    pub creator_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Claimer wallet, receives native SOL payouts
+    #[account(mut, address = claim.claimer)]
+    pub claimer: UncheckedAccount<'info>,
+
+    // the vote that reaches quorum must not pick where the money goes
+    #[account(mut, constraint = claimer_token_account.owner == claim.claimer)]
+    pub claimer_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = treasury.owner == config.treasury
+            && treasury.mint == quest.reward_mint @ QuestError::InvalidTreasury
+    )]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Scratch account for unwrapping native SOL payouts
+    #[account(mut, seeds = [b"unwrap", quest.key().as_ref()], bump)]
+    pub unwrap_account: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub oracle: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(attestation: Attestation)]
+pub struct ApproveWithAttestation<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, QuestConfig>,
+
+    #[account(seeds = [b"oracles"], bump = oracle_set.bump)]
+    pub oracle_set: Account<'info, OracleSet>,
+
+    #[account(mut)]
+    pub quest: Account<'info, Quest>,
+
+    #[account(
+        mut,
+        constraint = claim.quest == quest.key(),
+        constraint = claim.key() == attestation.claim @ QuestError::AttestationMismatch
+    )]
+    pub claim: Account<'info, Claim>,
+
+    #[account(mut, seeds = [b"stats", quest.creator.as_ref()], bump = creator_stats.bump)]
+    pub creator_stats: Account<'info, UserStats>,
+
+    #[account(mut, seeds = [b"stats", claim.claimer.as_ref()], bump = claimer_stats.bump)]
+    pub claimer_stats: Account<'info, UserStats>,
+
+    #[account(mut, seeds = [b"profile", claim.claimer.as_ref()], bump = profile.bump)]
+    pub profile: Account<'info, UserProfile>,
+
+    #[account(mut, seeds = [b"escrow", quest.key().as_ref()], bump)]
+    pub escrow: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, address = quest.reward_mint)]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: Creator wallet, gets quest and escrow rent back once the quest is settled
+    #[account(mut, address = quest.creator)]
+    pub creator: UncheckedAccount<'info>,
+
+    #[account(mut, constraint = creator_token_account.owner == quest.creator)]
+    pub creator_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Claimer wallet, receives native SOL payouts
+    #[account(mut, address = claim.claimer)]
+    pub claimer: UncheckedAccount<'info>,
+
+    // relayer is untrusted, so payouts can only go to the claimer and treasury
+    #[account(mut, constraint = claimer_token_account.owner == claim.claimer)]
+    pub claimer_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = treasury.owner == config.treasury
+            && treasury.mint == quest.reward_mint @ QuestError::InvalidTreasury
+    )]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Scratch account for unwrapping native SOL payouts
+    #[account(mut, seeds = [b"unwrap", quest.key().as_ref()], bump)]
+    pub unwrap_account: UncheckedAccount<'info>,
+
+    /// CHECK: Instructions sysvar, read to find the Ed25519 signature instruction
+    #[account(address = ix_sysvar::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    /// CHECK: Anyone can relay a signed attestation
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(attestation: Attestation)]
+pub struct RejectWithAttestation<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, QuestConfig>,
+
+    #[account(seeds = [b"oracles"], bump = oracle_set.bump)]
+    pub oracle_set: Account<'info, OracleSet>,
+
+    pub quest: Account<'info, Quest>,
+
+    #[account(
+        mut,
+        constraint = claim.quest == quest.key(),
+        constraint = claim.key() == attestation.claim @ QuestError::AttestationMismatch
+    )]
+    pub claim: Account<'info, Claim>,
+
+    /// CHECK: Instructions sysvar, read to find the Ed25519 signature instruction
+    #[account(address = ix_sysvar::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    /// CHECK: Anyone can relay a signed attestation
+    pub relayer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RejectCompletion<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, QuestConfig>,
+
+    #[account(seeds = [b"oracles"], bump = oracle_set.bump)]
+    pub oracle_set: Account<'info, OracleSet>,
+
+    pub quest: Account<'info, Quest>,
+
+    #[account(mut, constraint = claim.quest == quest.key())]
+    pub claim: Account<'info, Claim>,
+
+    pub oracle: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AbandonClaim<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, QuestConfig>,
+
+    #[account(mut)]
+    pub quest: Account<'info, Quest>,
+
+    #[account(
+        mut,
+        seeds = [b"claim", quest.key().as_ref(), claimer.key().as_ref()],
+        bump = claim.bump
+    )]
+    pub claim: Account<'info, Claim>,
+
+    #[account(mut, seeds = [b"profile", claimer.key().as_ref()], bump = profile.bump)]
+    pub profile: Account<'info, UserProfile>,
+
+    #[account(mut, seeds = [b"stats", claimer.key().as_ref()], bump = claimer_stats.bump)]
+    pub claimer_stats: Account<'info, UserStats>,
+
+    #[account(mut, seeds = [b"escrow", quest.key().as_ref()], bump)]
+    pub escrow: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, address = quest.reward_mint)]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: Creator wallet, receives native SOL payouts
+    #[account(mut, address = quest.creator)]
+    pub creator: UncheckedAccount<'info>,
+
+    #[account(mut, constraint = creator_token_account.owner == quest.creator)]
+    pub creator_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = treasury.owner == config.treasury
+            && treasury.mint == quest.reward_mint @ QuestError::InvalidTreasury
+    )]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Scratch account for unwrapping native SOL payouts
+    #[account(mut, seeds = [b"unwrap", quest.key().as_ref()], bump)]
+    pub unwrap_account: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub claimer: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExpireClaim<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, QuestConfig>,
+
+    #[account(mut)]
+    pub quest: Account<'info, Quest>,
+
+    #[account(mut, constraint = claim.quest == quest.key())]
+    pub claim: Account<'info, Claim>,
+
+    #[account(mut, seeds = [b"profile", claim.claimer.as_ref()], bump = profile.bump)]
+    pub profile: Account<'info, UserProfile>,
+
+    #[account(mut, seeds = [b"stats", claim.claimer.as_ref()], bump = claimer_stats.bump)]
+    pub claimer_stats: Account<'info, UserStats>,
+
+    #[account(mut, seeds = [b"escrow", quest.key().as_ref()], bump)]
+    pub escrow: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, address = quest.reward_mint)]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: Creator wallet, receives native SOL payouts
+    #[account(mut, address = quest.creator)]
+    pub creator: UncheckedAccount<'info>,
+
+    #[account(mut, constraint = creator_token_account.owner == quest.creator)]
+    pub creator_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = treasury.owner == config.treasury
+            && treasury.mint == quest.reward_mint @ QuestError::InvalidTreasury
+    )]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Scratch account for unwrapping native SOL payouts
+    #[account(mut, seeds = [b"unwrap", quest.key().as_ref()], bump)]
+    pub unwrap_account: UncheckedAccount<'info>,
+
+    /// CHECK: Anyone can call this (permissionless crank)
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AutoApprove<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, QuestConfig>,
+
+    #[account(mut)]
+    pub quest: Account<'info, Quest>,
+
+    #[account(mut, constraint = claim.quest == quest.key())]
+    pub claim: Account<'info, Claim>,
+
+    #[account(mut, seeds = [b"stats", quest.creator.as_ref()], bump = creator_stats.bump)]
+    pub creator_stats: Account<'info, UserStats>,
+
+    #[account(mut, seeds = [b"stats", claim.claimer.as_ref()], bump = claimer_stats.bump)]
+    pub claimer_stats: Account<'info, UserStats>,
+
+    #[account(mut, seeds = [b"profile", claim.claimer.as_ref()], bump = profile.bump)]
+    pub profile: Account<'info, UserProfile>,
+
+    #[account(mut, seeds = [b"escrow", quest.key().as_ref()], bump)]
+    pub escrow: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, address = quest.reward_mint)]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: Creator wallet, gets quest and escrow rent back once the quest is settled
+    #[account(mut, address = quest.creator)]
+    pub creator: UncheckedAccount<'info>,
+
+    #[account(mut, constraint = creator_token_account.owner == quest.creator)]
+    pub creator_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Claimer wallet, receives native SOL payouts
+    #[account(mut, address = claim.claimer)]
+    pub claimer: UncheckedAccount<'info>,
+
+    // anyone can crank this, so payouts can only go to the claimer and treasury
+    #[account(mut, constraint = claimer_token_account.owner == claim.claimer)]
+    pub claimer_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = treasury.owner == config.treasury
+            && treasury.mint == quest.reward_mint @ QuestError::InvalidTreasury
+    )]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Scratch account for unwrapping native SOL payouts
+    #[account(mut, seeds = [b"unwrap", quest.key().as_ref()], bump)]
+    pub unwrap_account: UncheckedAccount<'info>,
+
+    /// CHECK: Anyone can call this (permissionless crank)
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreatorApprove<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, QuestConfig>,
+
+    #[account(mut)]
+    pub quest: Account<'info, Quest>,
+
+    #[account(mut, constraint = claim.quest == quest.key())]
+    pub claim: Account<'info, Claim>,
+
+    #[account(mut, seeds = [b"stats", quest.creator.as_ref()], bump = creator_stats.bump)]
+    pub creator_stats: Account<'info, UserStats>,
+
+    #[account(mut, seeds = [b"stats", claim.claimer.as_ref()], bump = claimer_stats.bump)]
+    pub claimer_stats: Account<'info, UserStats>,
+
+    #[account(mut, seeds = [b"profile", claim.claimer.as_ref()], bump = profile.bump)]
+    pub profile: Account<'info, UserProfile>,
+
+    #[account(mut, seeds = [b"escrow", quest.key().as_ref()], bump)]
+    pub escrow: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, address = quest.reward_mint)]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, constraint = creator_token_account.owner == quest.creator)]
+    pub creator_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Claimer wallet, receives native SOL payouts
+    #[account(mut, address = claim.claimer)]
+    pub claimer: UncheckedAccount<'info>,
+
+    #[account(mut, constraint = claimer_token_account.owner == claim.claimer)]
+    pub claimer_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The creator signs here, so the fee destination is pinned to the configured treasury
+    #[account(
+        mut,
+        constraint = treasury.owner == config.treasury
+            && treasury.mint == quest.reward_mint @ QuestError::InvalidTreasury
+    )]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Scratch account for unwrapping native SOL payouts
+    #[account(mut, seeds = [b"unwrap", quest.key().as_ref()], bump)]
+    pub unwrap_account: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreatorReject<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, QuestConfig>,
+
+    pub quest: Account<'info, Quest>,
+
+    #[account(mut, constraint = claim.quest == quest.key())]
+    pub claim: Account<'info, Claim>,
+
+    pub creator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RequestRevision<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, QuestConfig>,
+
+    #[account(seeds = [b"oracles"], bump = oracle_set.bump)]
+    pub oracle_set: Account<'info, OracleSet>,
+
+    pub quest: Account<'info, Quest>,
+
+    #[account(mut, constraint = claim.quest == quest.key())]
+    pub claim: Account<'info, Claim>,
+
+    #[account(mut, seeds = [b"profile", claim.claimer.as_ref()], bump = profile.bump)]
+    pub profile: Account<'info, UserProfile>,
+
+    /// Quest creator or a registered oracle
+    pub reviewer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeRejection<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, QuestConfig>,
+
+    #[account(mut)]
+    pub quest: Account<'info, Quest>,
+
+    #[account(mut, constraint = claim.quest == quest.key())]
+    pub claim: Account<'info, Claim>,
+
+    /// Takes a strike if the rejection was safety-flagged
+    #[account(mut, seeds = [b"profile", claim.claimer.as_ref()], bump = profile.bump)]
+    pub profile: Account<'info, UserProfile>,
+
+    #[account(mut, seeds = [b"stats", claim.claimer.as_ref()], bump = claimer_stats.bump)]
+    pub claimer_stats: Account<'info, UserStats>,
+
+    #[account(mut, seeds = [b"escrow", quest.key().as_ref()], bump)]
+    pub escrow: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, address = quest.reward_mint)]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: Creator wallet, receives native SOL payouts
+    #[account(mut, address = quest.creator)]
+    pub creator: UncheckedAccount<'info>,
+
+    #[account(mut, constraint = creator_token_account.owner == quest.creator)]
+    pub creator_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Claimer wallet, receives native SOL payouts
+    #[account(mut, address = claim.claimer)]
+    pub claimer: UncheckedAccount<'info>,
+
+    #[account(mut, constraint = claimer_token_account.owner == claim.claimer)]
+    pub claimer_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = treasury.owner == config.treasury
+            && treasury.mint == quest.reward_mint @ QuestError::InvalidTreasury
+    )]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Scratch account for unwrapping native SOL payouts
+    #[account(mut, seeds = [b"unwrap", quest.key().as_ref()], bump)]
+    pub unwrap_account: UncheckedAccount<'info>,
+
+    /// CHECK: Anyone can call this (permissionless crank)
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct OpenDispute<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, QuestConfig>,
+
+    pub quest: Account<'info, Quest>,
+
+    #[account(
+        mut,
+        seeds = [b"claim", quest.key().as_ref(), claimer.key().as_ref()],
+        bump = claim.bump
+    )]
+    pub claim: Account<'info, Claim>,
+
+    #[account(
+        init,
+        payer = claimer,
+        space = 8 + Dispute::INIT_SPACE,
+        seeds = [b"dispute", claim.key().as_ref()],
+        bump
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    #[account(mut)]
+    pub claimer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CastVote<'info> {
+    #[account(seeds = [b"jurors"], bump = juror_set.bump)]
+    pub juror_set: Account<'info, JurorSet>,
+
+    #[account(mut, seeds = [b"dispute", dispute.claim.as_ref()], bump = dispute.bump)]
+    pub dispute: Account<'info, Dispute>,
+
+    pub juror: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveDispute<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, QuestConfig>,
+
+    #[account(mut)]
+    pub quest: Account<'info, Quest>,
+
+    #[account(mut, constraint = claim.quest == quest.key())]
+    pub claim: Account<'info, Claim>,
+
+    /// Closed to the claimer, who paid its rent and gets any unforfeited bond back
+    #[account(
+        mut,
+        close = claimer,
+        seeds = [b"dispute", claim.key().as_ref()],
+        bump = dispute.bump
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    /// Takes a strike if the rejection was safety-flagged
+    #[account(mut, seeds = [b"profile", claim.claimer.as_ref()], bump = profile.bump)]
+    pub profile: Account<'info, UserProfile>,
+
+    #[account(mut, seeds = [b"stats", quest.creator.as_ref()], bump = creator_stats.bump)]
+    pub creator_stats: Account<'info, UserStats>,
+
+    #[account(mut, seeds = [b"stats", claim.claimer.as_ref()], bump = claimer_stats.bump)]
+    pub claimer_stats: Account<'info, UserStats>,
+
+    #[account(mut, seeds = [b"escrow", quest.key().as_ref()], bump)]
+    pub escrow: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, address = quest.reward_mint)]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: Creator wallet, receives native SOL payouts
+    #[account(mut, address = quest.creator)]
+    pub creator: UncheckedAccount<'info>,
+
+    #[account(mut, constraint = creator_token_account.owner == quest.creator)]
+    pub creator_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Claimer wallet, receives native SOL payouts
+    #[account(mut, address = claim.claimer)]
+    pub claimer: UncheckedAccount<'info>,
+
+    #[account(mut, constraint = claimer_token_account.owner == claim.claimer)]
+    pub claimer_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = treasury.owner == config.treasury
+            && treasury.mint == quest.reward_mint @ QuestError::InvalidTreasury
+    )]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Scratch account for unwrapping native SOL payouts
+    #[account(mut, seeds = [b"unwrap", quest.key().as_ref()], bump)]
+    pub unwrap_account: UncheckedAccount<'info>,
+
+    /// CHECK: Anyone can call this (permissionless crank)
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CancelQuest<'info> {
+    #[account(mut)]
+    pub quest: Account<'info, Quest>,
+
+    #[account(mut, seeds = [b"escrow", quest.key().as_ref()], bump)]
+    pub escrow: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, address = quest.reward_mint)]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(mut, constraint = creator_token_account.owner == creator.key())]
+    pub creator_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Scratch account for unwrapping native SOL payouts
+    #[account(mut, seeds = [b"unwrap", quest.key().as_ref()], bump)]
+    pub unwrap_account: UncheckedAccount<'info>,
+
+    /// Only passed when the description text is stored on-chain
+    #[account(
+        mut,
+        seeds = [b"description", quest.key().as_ref()],
+        bump = quest_description.bump
+    )]
+    pub quest_description: Option<Account<'info, QuestDescription>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CloseClaim<'info> {
+    /// CHECK: May already be closed; deserialized in the handler if it still exists
+    #[account(address = claim.quest)]
+    pub quest: UncheckedAccount<'info>,
+
+    #[account(mut, close = claimer)]
+    pub claim: Account<'info, Claim>,
+
+    /// CHECK: Claimer wallet, paid the claim rent
+    #[account(mut, address = claim.claimer)]
+    pub claimer: UncheckedAccount<'info>,
+
+    /// CHECK: Anyone can call this (permissionless crank)
+    pub cranker: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseProof<'info> {
+    /// CHECK: May already be closed; deserialized in the handler if it still exists
+    #[account(address = proof.claim)]
+    pub claim: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        close = claimer,
+        seeds = [b"proof", proof.claim.as_ref()],
+        bump = proof.bump
+    )]
+    pub proof: Account<'info, Proof>,
+
+    /// CHECK: Claimer wallet, paid the proof rent
+    #[account(mut, address = proof.claimer)]
+    pub claimer: UncheckedAccount<'info>,
+
+    /// CHECK: Anyone can call this (permissionless crank)
+    pub cranker: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseQuest<'info> {
+    #[account(mut)]
+    pub quest: Account<'info, Quest>,
+
+    #[account(mut, seeds = [b"escrow", quest.key().as_ref()], bump)]
+    pub escrow: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, address = quest.reward_mint)]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: Creator wallet, paid the quest and escrow rent
+    #[account(mut, address = quest.creator)]
+    pub creator: UncheckedAccount<'info>,
+
+    #[account(mut, constraint = creator_token_account.owner == quest.creator)]
+    pub creator_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Scratch account for unwrapping native SOL payouts
+    #[account(mut, seeds = [b"unwrap", quest.key().as_ref()], bump)]
+    pub unwrap_account: UncheckedAccount<'info>,
+
+    /// Only passed when the description text is stored on-chain
+    #[account(
+        mut,
+        seeds = [b"description", quest.key().as_ref()],
+        bump = quest_description.bump
+    )]
+    pub quest_description: Option<Account<'info, QuestDescription>>,
+
+    /// CHECK: Anyone can call this (permissionless crank)
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CloseDescription<'info> {
+    /// CHECK: May already be closed; deserialized in the handler if it still exists
+    #[account(address = quest_description.quest)]
+    pub quest: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        close = creator,
+        seeds = [b"description", quest_description.quest.as_ref()],
+        bump = quest_description.bump
+    )]
+    pub quest_description: Account<'info, QuestDescription>,
+
+    /// CHECK: Creator wallet, paid the description rent
+    #[account(mut, address = quest_description.creator)]
+    pub creator: UncheckedAccount<'info>,
+
+    /// CHECK: Anyone can call this (permissionless crank)
+    pub cranker: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AddToReward<'info> {
+    #[account(mut)]
+    pub quest: Account<'info, Quest>,
+
+    #[account(
+        init_if_needed,
+        payer = contributor,
+        space = 8 + Contribution::INIT_SPACE,
+        seeds = [b"contribution", quest.key().as_ref(), contributor.key().as_ref()],
+        bump
+    )]
+    pub contribution: Account<'info, Contribution>,
+
+    #[account(mut, seeds = [b"escrow", quest.key().as_ref()], bump)]
+    pub escrow: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = quest.reward_mint)]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub contributor: Signer<'info>,
+
+    /// Not needed for native SOL quests
+    #[account(mut, constraint = contributor_token_account.owner == contributor.key())]
+    pub contributor_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RefundContribution<'info> {
+    #[account(mut)]
+    pub quest: Account<'info, Quest>,
+
+    #[account(
+        mut,
+        close = contributor,
+        seeds = [b"contribution", quest.key().as_ref(), contributor.key().as_ref()],
+        bump = contribution.bump
+    )]
+    pub contribution: Account<'info, Contribution>,
+
+    #[account(mut, seeds = [b"escrow", quest.key().as_ref()], bump)]
+    pub escrow: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, address = quest.reward_mint)]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: Contributor wallet, receives the refund and the contribution rent
+    #[account(mut, address = contribution.contributor)]
+    pub contributor: UncheckedAccount<'info>,
+
+    #[account(mut, constraint = contributor_token_account.owner == contribution.contributor)]
+    pub contributor_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Creator wallet, gets quest and escrow rent back after the last refund
+    #[account(mut, address = quest.creator)]
+    pub creator: UncheckedAccount<'info>,
+
+    #[account(mut, constraint = creator_token_account.owner == quest.creator)]
+    pub creator_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Scratch account for unwrapping native SOL payouts
+    #[account(mut, seeds = [b"unwrap", quest.key().as_ref()], bump)]
+    pub unwrap_account: UncheckedAccount<'info>,
+
+    /// CHECK: Anyone can call this (permissionless crank)
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExpireQuest<'info> {
+    #[account(mut)]
+    pub quest: Account<'info, Quest>,
+
+    #[account(mut, seeds = [b"escrow", quest.key().as_ref()], bump)]
+    pub escrow: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, address = quest.reward_mint)]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: Creator wallet, receives the refund and rent
+    #[account(mut, address = quest.creator)]
+    pub creator: UncheckedAccount<'info>,
+
+    #[account(mut, constraint = creator_token_account.owner == quest.creator)]
+    pub creator_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Scratch account for unwrapping native SOL payouts
+    #[account(mut, seeds = [b"unwrap", quest.key().as_ref()], bump)]
+    pub unwrap_account: UncheckedAccount<'info>,
+
+    /// CHECK: Anyone can call this (permissionless crank)
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+// ============================================================================
+// ESCROW
+// ============================================================================
+
+/// Moves a deposit into a quest escrow and returns the amount that arrived. Native
+/// SOL is wrapped straight from the depositor's lamports, SPL tokens come from their
+/// token account.
+fn fund_escrow<'info>(
+    token_program: &Interface<'info, TokenInterface>,
+    system_program: &Program<'info, System>,
+    escrow: &mut InterfaceAccount<'info, TokenAccount>,
+    mint: &InterfaceAccount<'info, Mint>,
+    depositor: &Signer<'info>,
+    depositor_token_account: Option<&InterfaceAccount<'info, TokenAccount>>,
+    amount: u64,
+) -> Result<u64> {
+    if escrow.mint == native_mint::ID {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                system_program::Transfer {
+                    from: depositor.to_account_info(),
+                    to: escrow.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+        token_interface::sync_native(CpiContext::new(
+            token_program.to_account_info(),
+            SyncNative {
+                account: escrow.to_account_info(),
+            },
+        ))?;
+        return Ok(amount);
+    }
+
+    let from = depositor_token_account.ok_or(QuestError::MissingTokenAccount)?;
+    let balance_before = escrow.amount;
+    token_interface::transfer_checked(
+        CpiContext::new(
+            token_program.to_account_info(),
+            TransferChecked {
+                from: from.to_account_info(),
+                mint: mint.to_account_info(),
+                to: escrow.to_account_info(),
+                authority: depositor.to_account_info(),
+            },
+        ),
+        amount,
+        mint.decimals,
+    )?;
+
+    escrow.reload()?;
+    Ok(escrow.amount
+        .checked_sub(balance_before)
+        .ok_or(QuestError::Overflow)?)
+}
+
+/// Sweeps what is left in escrow to the creator and returns the quest and escrow
+/// rent to them. Only call once the quest is settled and holds no live stakes.
+/// With contributions outstanding, their share stays in escrow and the quest
+/// stays open until the last refund_contribution.
+fn close_quest_accounts<'info>(
+    quest: &mut Account<'info, Quest>,
+    payout: &EscrowPayout<'_, 'info>,
+    creator: AccountInfo<'info>,
+    creator_token_account: Option<&InterfaceAccount<'info, TokenAccount>>,
+    description: Option<&Account<'info, QuestDescription>>,
+) -> Result<()> {
+    quest.refunds_ready = true;
+    if quest.contributors > 0 {
+        let unreserved = payout.balance()?.saturating_sub(quest.contributor_refund_pool);
+        let creator_refund = reserve_contributor_refund(quest, unreserved)?;
+        if creator_refund > 0 {
+            payout.pay(creator_token_account, &creator, creator_refund)?;
+        }
+        return Ok(());
+    }
+
+    payout.close(creator_token_account, creator.clone())?;
+    if let Some(description) = description {
+        description.close(creator.clone())?;
+    }
+    quest.close(creator)?;
+
+    emit!(QuestClosed {
+        quest_id: quest.id,
+        quest: quest.key(),
+        creator: quest.creator,
+    });
+
+    Ok(())
+}
+
+/// Sets aside the contributors' pro-rata share of reward being returned and
+/// returns the creator's share.
+fn reserve_contributor_refund(quest: &mut Quest, amount: u64) -> Result<u64> {
+    if quest.contributed_amount == 0 {
+        return Ok(amount);
+    }
+
+    let funded = quest.creator_funded
+        .checked_add(quest.contributed_amount)
+        .ok_or(QuestError::Overflow)?;
+    let reserved = (amount as u128)
+        .checked_mul(quest.contributed_amount as u128)
+        .ok_or(QuestError::Overflow)?
+        .checked_div(funded as u128)
+        .ok_or(QuestError::Overflow)? as u64;
+    quest.contributor_refund_pool = quest.contributor_refund_pool
+        .checked_add(reserved)
+        .ok_or(QuestError::Overflow)?;
+
+    Ok(amount - reserved)
+}
+
+/// Accounts needed to move funds out of a quest escrow, signed by the quest PDA.
+struct EscrowPayout<'a, 'info> {
+    quest: AccountInfo<'info>,
+    signer_seeds: &'a [&'a [&'a [u8]]],
+    escrow: &'a InterfaceAccount<'info, TokenAccount>,
+    reward_mint: &'a InterfaceAccount<'info, Mint>,
+    unwrap_account: &'a UncheckedAccount<'info>,
+    unwrap_bump: u8,
+    /// Transaction signer; fronts rent for the unwrap account and is refunded on close
+    payer: AccountInfo<'info>,
+    token_program: &'a Interface<'info, TokenInterface>,
+    system_program: &'a Program<'info, System>,
+}
+
+impl<'a, 'info> EscrowPayout<'a, 'info> {
+    /// Pays a user out of escrow: native SOL is unwrapped to their wallet, SPL
+    /// tokens go to their token account.
+    fn pay(
+        &self,
+        token_account: Option<&InterfaceAccount<'info, TokenAccount>>,
+        wallet: &impl ToAccountInfo<'info>,
+        amount: u64,
+    ) -> Result<()> {
+        if self.reward_mint.key() == native_mint::ID {
+            return self.unwrap_to(wallet.to_account_info(), amount);
+        }
+        let to = token_account.ok_or(QuestError::MissingTokenAccount)?;
+        self.transfer(to.to_account_info(), amount)
+    }
+
+    /// Pays a guild member through an unvalidated remaining account: their
+    /// wallet for native SOL, otherwise their token account for the reward mint.
+    fn pay_member(
+        &self,
+        member: &GuildMember,
+        account: &AccountInfo<'info>,
+        amount: u64,
+    ) -> Result<()> {
+        if self.reward_mint.key() == native_mint::ID {
+            require!(account.key() == member.wallet, QuestError::GuildAccountsMismatch);
+            return self.unwrap_to(account.clone(), amount);
+        }
+        require!(
+            account.owner == &self.token_program.key(),
+            QuestError::GuildAccountsMismatch
+        );
+        let token_account = TokenAccount::try_deserialize(&mut &account.try_borrow_data()?[..])?;
+        require!(
+            token_account.owner == member.wallet,
+            QuestError::GuildAccountsMismatch
+        );
+        self.transfer(account.clone(), amount)
+    }
+
+    fn transfer(&self, to: AccountInfo<'info>, amount: u64) -> Result<()> {
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                TransferChecked {
+                    from: self.escrow.to_account_info(),
+                    mint: self.reward_mint.to_account_info(),
+                    to,
+                    authority: self.quest.clone(),
+                },
+                self.signer_seeds,
+            ),
+            amount,
+            self.reward_mint.decimals,
+        )
+    }
+
+    fn burn(&self, amount: u64) -> Result<()> {
+        token_interface::burn(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                Burn {
+                    mint: self.reward_mint.to_account_info(),
+                    from: self.escrow.to_account_info(),
+                    authority: self.quest.clone(),
+                },
+                self.signer_seeds,
+            ),
+            amount,
+        )
+    }
+
+    /// Empties the escrow into the given accounts and closes it. Closing a wrapped
+    /// SOL account releases its whole balance, so only SPL tokens need a sweep.
+    /// Current escrow balance; earlier payouts in this instruction leave the
+    /// deserialized copy stale.
+    fn balance(&self) -> Result<u64> {
+        let escrow_info = self.escrow.to_account_info();
+        let data = escrow_info.try_borrow_data()?;
+        Ok(TokenAccount::try_deserialize(&mut &data[..])?.amount)
+    }
+
+    fn close(
+        &self,
+        token_account: Option<&InterfaceAccount<'info, TokenAccount>>,
+        wallet: AccountInfo<'info>,
+    ) -> Result<()> {
+        if self.reward_mint.key() != native_mint::ID {
+            let escrow_info = self.escrow.to_account_info();
+            let remaining = self.balance()?;
+            if remaining > 0 {
+                let to = token_account.ok_or(QuestError::MissingTokenAccount)?;
+                self.transfer(to.to_account_info(), remaining)?;
+            }
+
+            // Token-2022 refuses to close accounts still holding withheld transfer fees
+            let mint_info = self.reward_mint.to_account_info();
+            if self.token_program.key() == token_2022::ID
+                && token_interface::get_mint_extension_data::<TransferFeeConfig>(&mint_info).is_ok()
+            {
+                token_interface::harvest_withheld_tokens_to_mint(
+                    CpiContext::new(
+                        self.token_program.to_account_info(),
+                        HarvestWithheldTokensToMint {
+                            token_program_id: self.token_program.to_account_info(),
+                            mint: mint_info,
+                        },
+                    ),
+                    vec![escrow_info],
+                )?;
+            }
+        }
+
+        token_interface::close_account(CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            CloseAccount {
+                account: self.escrow.to_account_info(),
+                destination: wallet,
+                authority: self.quest.clone(),
+            },
+            self.signer_seeds,
+        ))
+    }
+
+    /// Wrapped SOL can only leave a token account by closing it, so the amount is
+    /// moved into a scratch account, closed to the payer, then forwarded as lamports.
+    fn unwrap_to(&self, wallet: AccountInfo<'info>, amount: u64) -> Result<()> {
+        let quest_key = self.quest.key();
+        let unwrap_seeds: &[&[u8]] = &[b"unwrap", quest_key.as_ref(), &[self.unwrap_bump]];
+        let unwrap = self.unwrap_account.to_account_info();
+        // Only the legacy token program has a native mint, so no extensions to size for
+        let space = token::TokenAccount::LEN;
+        let rent = Rent::get()?.minimum_balance(space);
+
+        // Someone may have pre-funded the address, so top up and allocate instead
+        // of create_account, which refuses accounts that already hold lamports
+        if unwrap.lamports() == 0 {
+            system_program::create_account(
+                CpiContext::new_with_signer(
+                    self.system_program.to_account_info(),
+                    CreateAccount {
+                        from: self.payer.clone(),
+                        to: unwrap.clone(),
+                    },
+                    &[unwrap_seeds],
+                ),
+                rent,
+                space as u64,
+                &token::ID,
+            )?;
+        } else {
+            let shortfall = rent.saturating_sub(unwrap.lamports());
+            if shortfall > 0 {
+                system_program::transfer(
+                    CpiContext::new(
+                        self.system_program.to_account_info(),
+                        system_program::Transfer {
+                            from: self.payer.clone(),
+                            to: unwrap.clone(),
+                        },
+                    ),
+                    shortfall,
+                )?;
+            }
+            system_program::allocate(
+                CpiContext::new_with_signer(
+                    self.system_program.to_account_info(),
+                    system_program::Allocate {
+                        account_to_allocate: unwrap.clone(),
+                    },
+                    &[unwrap_seeds],
+                ),
+                space as u64,
+            )?;
+            system_program::assign(
+                CpiContext::new_with_signer(
+                    self.system_program.to_account_info(),
+                    system_program::Assign {
+                        account_to_assign: unwrap.clone(),
+                    },
+                    &[unwrap_seeds],
+                ),
+                &token::ID,
+            )?;
+        }
+
+        token_interface::initialize_account3(CpiContext::new(
+            self.token_program.to_account_info(),
+            InitializeAccount3 {
+                account: unwrap.clone(),
+                mint: self.reward_mint.to_account_info(),
+                authority: self.quest.clone(),
+            },
+        ))?;
+
+        self.transfer(unwrap.clone(), amount)?;
+
+        token_interface::close_account(CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            CloseAccount {
+                account: unwrap,
+                destination: self.payer.clone(),
+                authority: self.quest.clone(),
+            },
+            self.signer_seeds,
+        ))?;
+
+        if self.payer.key() != wallet.key() {
+            system_program::transfer(
+                CpiContext::new(
+                    self.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: self.payer.clone(),
+                        to: wallet,
+                    },
+                ),
+                amount,
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+// ============================================================================
+// SETTLEMENT
+// ============================================================================
+
+/// Wallets and token accounts a settlement can pay out to.
+struct SettlementParties<'a, 'info> {
+    creator: AccountInfo<'info>,
+    creator_token_account: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+    claimer: AccountInfo<'info>,
+    claimer_token_account: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+    /// Guild members' payout accounts, in member order
+    members: &'a [AccountInfo<'info>],
+}
+
+/// Reputation records an approval updates.
+struct SettlementStats<'a> {
+    creator: &'a mut UserStats,
+    claimer: &'a mut UserStats,
+    /// Claimer's profile, whose active claim count covers claims awaiting proof
+    profile: &'a mut UserProfile,
+}
+
+/// Records (or replaces) an oracle's verdict on a claim and reports whether the
+/// threshold of matching verdicts from current set members has been reached.
+fn record_oracle_vote(
+    oracle_set: &OracleSet,
+    quest: &Account<'_, Quest>,
+    claim: &mut Claim,
+    oracle: Pubkey,
+    verdict: OracleVerdict,
+) -> Result<bool> {
+    require!(oracle_set.oracles.contains(&oracle), QuestError::NotOracle);
+
+    match claim.oracle_votes.iter_mut().find(|v| v.oracle == oracle) {
+        Some(vote) => vote.verdict = verdict,
+        None => claim.oracle_votes.push(OracleVote { oracle, verdict }),
+    }
+
+    let votes = claim
+        .oracle_votes
+        .iter()
+        .filter(|v| v.verdict == verdict && oracle_set.oracles.contains(&v.oracle))
+        .count();
+
+    emit!(OracleVoteCast {
+        quest_id: quest.id,
+        quest: quest.key(),
+        claimer: claim.claimer,
+        oracle,
+        verdict,
+        votes: votes as u8,
+        threshold: oracle_set.threshold,
+    });
+
+    Ok(votes >= oracle_set.threshold as usize)
+}
+
+/// Size of one Ed25519SignatureOffsets entry in the precompile's instruction data
+const ED25519_OFFSETS_LEN: usize = 14;
+/// Ed25519 offsets index meaning "this instruction"
+const ED25519_SELF_INDEX: u16 = u16::MAX;
+/// Prefix of every signed attestation, ahead of the program id
+const ATTESTATION_DOMAIN: &[u8] = b"quest-attestation-v1";
+
+/// Bytes an oracle signs for `attestation`; the prefix keeps a signature from
+/// being replayed against another program or message type
+fn attestation_message(attestation: &Attestation) -> Result<Vec<u8>> {
+    let mut message = [ATTESTATION_DOMAIN, crate::ID.as_ref()].concat();
+    attestation.serialize(&mut message)?;
+    Ok(message)
+}
+
+/// Returns the keys that signed `attestation` in the Ed25519 instruction placed
+/// right before the current one. The precompile has already checked the
+/// signatures by the time this runs; this only confirms what was signed.
+fn attestation_signers(
+    instructions: &AccountInfo,
+    attestation: &Attestation,
+) -> Result<Vec<Pubkey>> {
+    let current = ix_sysvar::load_current_index_checked(instructions)?;
+    require!(current > 0, QuestError::MissingAttestation);
+    let ix = ix_sysvar::load_instruction_at_checked((current - 1) as usize, instructions)?;
+    require!(ix.program_id == ed25519_program::ID, QuestError::MissingAttestation);
+
+    let message = attestation_message(attestation)?;
+    let data = &ix.data;
+    let count = *data.first().ok_or(QuestError::InvalidAttestation)? as usize;
+    require!(count > 0, QuestError::MissingAttestation);
+
+    let read_u16 = |at: usize| -> Result<u16> {
+        let bytes = data.get(at..at + 2).ok_or(QuestError::InvalidAttestation)?;
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+    };
+
+    let mut signers = Vec::with_capacity(count);
+    for i in 0..count {
+        let base = 2 + i * ED25519_OFFSETS_LEN;
+        // everything must live inside the Ed25519 instruction itself, otherwise a
+        // relayer could point the precompile at bytes that differ from `message`
+        require!(
+            read_u16(base + 2)? == ED25519_SELF_INDEX
+                && read_u16(base + 6)? == ED25519_SELF_INDEX
+                && read_u16(base + 12)? == ED25519_SELF_INDEX,
+            QuestError::InvalidAttestation
+        );
+        let pubkey_at = read_u16(base + 4)? as usize;
+        let message_at = read_u16(base + 8)? as usize;
+        let message_len = read_u16(base + 10)? as usize;
+
+        let signed = data
+            .get(message_at..message_at + message_len)
+            .ok_or(QuestError::InvalidAttestation)?;
+        require!(signed == message.as_slice(), QuestError::AttestationMismatch);
+
+        let pubkey = data
+            .get(pubkey_at..pubkey_at + 32)
+            .ok_or(QuestError::InvalidAttestation)?;
+        signers.push(Pubkey::try_from(pubkey).map_err(|_| QuestError::InvalidAttestation)?);
+    }
+
+    Ok(signers)
+}
+
+/// Consumes the attestation nonce and records one vote per signing oracle.
+/// Returns whether the quorum was reached.
+fn record_attestation(
+    oracle_set: &OracleSet,
+    quest: &Account<'_, Quest>,
+    claim: &mut Claim,
+    attestation: &Attestation,
+    signers: &[Pubkey],
+) -> Result<bool> {
+    require!(
+        claim.proof_hash == Some(attestation.proof_hash),
+        QuestError::AttestationMismatch
+    );
+    let next_nonce = claim
+        .attestation_nonce
+        .checked_add(1)
+        .ok_or(QuestError::Overflow)?;
+    require!(
+        attestation.nonce == next_nonce,
+        QuestError::StaleAttestationNonce
+    );
+    claim.attestation_nonce = next_nonce;
+
+    let verdict = match attestation.decision {
+        AttestationDecision::Approve => OracleVerdict::Approve,
+        AttestationDecision::Reject if attestation.safety_flags != 0 => {
+            OracleVerdict::SafetyReject
+        }
+        AttestationDecision::Reject => OracleVerdict::Reject,
+    };
+
+    emit!(AttestationRelayed {
+        quest_id: quest.id,
+        quest: quest.key(),
+        claimer: claim.claimer,
+        decision: attestation.decision,
+        confidence: attestation.confidence,
+        safety_flags: attestation.safety_flags,
+        nonce: attestation.nonce,
+    });
+
+    let mut quorum = false;
+    for oracle in signers {
+        quorum = record_oracle_vote(oracle_set, quest, claim, *oracle, verdict)?;
+    }
+
+    Ok(quorum)
+}
+
+/// Sends the treasury and burn shares of a forfeited stake and returns the
+/// creator's share for the caller to pay. Native SOL cannot be burned, so that
+/// share goes to treasury as with protocol fees.
+fn forfeit_stake<'info>(
+    config: &QuestConfig,
+    quest: &Account<'info, Quest>,
+    claim: &Claim,
+    payout: &EscrowPayout<'_, 'info>,
+    treasury: AccountInfo<'info>,
+) -> Result<u64> {
+    let split = &config.forfeit_split;
+    let stake = claim.stake_amount;
+    let mut treasury_amount = stake
+        .checked_mul(split.treasury_bps as u64)
+        .ok_or(QuestError::Overflow)?
+        .checked_div(10000)
+        .ok_or(QuestError::Overflow)?;
+    let mut burn_amount = stake
+        .checked_mul(split.burn_bps as u64)
+        .ok_or(QuestError::Overflow)?
+        .checked_div(10000)
+        .ok_or(QuestError::Overflow)?;
+    if quest.reward_mint == native_mint::ID {
+        treasury_amount = treasury_amount
+            .checked_add(burn_amount)
+            .ok_or(QuestError::Overflow)?;
+        burn_amount = 0;
+    }
+    // Rounding dust stays with the creator
+    let creator_amount = stake
+        .checked_sub(treasury_amount)
+        .and_then(|rest| rest.checked_sub(burn_amount))
+        .ok_or(QuestError::Overflow)?;
+
+    if treasury_amount > 0 {
+        payout.transfer(treasury, treasury_amount)?;
+    }
+    if burn_amount > 0 {
+        payout.burn(burn_amount)?;
+    }
+
+    emit!(StakeForfeited {
+        quest_id: quest.id,
+        quest: quest.key(),
+        claimer: claim.claimer,
+        creator_amount,
+        treasury_amount,
+        burn_amount,
+    });
+
+    Ok(creator_amount)
+}
+
+/// Pays a claim's reward, plus its stake if `return_stake`. A solo claimer gets
+/// it all; a guild's reward is split by weight (the last member taking the
+/// rounding) and each member gets back the stake they posted.
+fn pay_claimers<'info>(
+    claim: &Claim,
+    payout: &EscrowPayout<'_, 'info>,
+    parties: &SettlementParties<'_, 'info>,
+    reward: u64,
+    return_stake: bool,
+) -> Result<()> {
+    if claim.members.is_empty() {
+        let amount = if return_stake {
+            reward.checked_add(claim.stake_amount).ok_or(QuestError::Overflow)?
+        } else {
+            reward
+        };
+        return payout.pay(parties.claimer_token_account, &parties.claimer, amount);
+    }
+
+    require!(
+        parties.members.len() == claim.members.len(),
+        QuestError::GuildAccountsMismatch
+    );
+    let mut unpaid = reward;
+    for (i, (member, account)) in claim.members.iter().zip(parties.members).enumerate() {
+        let share = if i + 1 == claim.members.len() {
+            unpaid
+        } else {
+            reward
+                .checked_mul(member.weight_bps as u64)
+                .ok_or(QuestError::Overflow)?
+                .checked_div(10000)
+                .ok_or(QuestError::Overflow)?
+        };
+        unpaid = unpaid.checked_sub(share).ok_or(QuestError::Overflow)?;
+
+        let amount = if return_stake {
+            share.checked_add(member.stake).ok_or(QuestError::Overflow)?
+        } else {
+            share
+        };
+        if amount > 0 {
+            payout.pay_member(member, account, amount)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn validate_guild(members: &[GuildShare], leader: Pubkey, creator: Pubkey) -> Result<()> {
+    let total_bps = members.iter().map(|m| m.weight_bps as u32).sum::<u32>();
+    let unique = members
+        .iter()
+        .enumerate()
+        .all(|(i, m)| members[..i].iter().all(|other| other.wallet != m.wallet));
+    require!(
+        (2..=MAX_GUILD_MEMBERS).contains(&members.len())
+            && members[0].wallet == leader
+            && members.iter().all(|m| m.weight_bps > 0)
+            && total_bps == 10000
+            && unique,
+        QuestError::InvalidGuild
+    );
+    require!(
+        members.iter().all(|m| m.wallet != creator),
+        QuestError::CannotClaimOwnQuest
+    );
+    Ok(())
+}
+
+/// A member's weighted share of the minimum stake
+fn guild_stake(min_stake: u64, weight_bps: u16) -> Result<u64> {
+    Ok(min_stake
+        .checked_mul(weight_bps as u64)
+        .ok_or(QuestError::Overflow)?
+        .checked_div(10000)
+        .ok_or(QuestError::Overflow)?)
+}
+
+/// Puts a rejected claim on hold: nothing leaves escrow until the dispute window
+/// closes or a dispute is resolved.
+fn mark_rejected(
+    quest: &Account<'_, Quest>,
+    claim: &mut Claim,
+    safety_flagged: bool,
+    settings: &DisputeSettings,
+) -> Result<()> {
+    let dispute_deadline = Clock::get()?
+        .unix_timestamp
+        .checked_add(settings.window_seconds)
+        .ok_or(QuestError::Overflow)?;
+
+    claim.status = ClaimStatus::RejectionPending;
+    claim.safety_flagged = safety_flagged;
+    claim.dispute_deadline = Some(dispute_deadline);
+
+    emit!(ClaimRejected {
+        quest_id: quest.id,
+        quest: quest.key(),
+        claimer: claim.claimer,
+        safety_flagged,
+        dispute_deadline,
+    });
+
+    Ok(())
+}
+
+/// Pays the claimer their reward less the protocol fee, plus their stake. Shared
+/// by every approval path (oracle, creator, auto-approve). A partial award pays
+/// `award_bps` of the reward and returns the rest to its funders.
+#[allow(clippy::too_many_arguments)]
+fn approve_claim<'info>(
+    config: &QuestConfig,
+    quest: &mut Account<'info, Quest>,
+    claim: &mut Account<'info, Claim>,
+    payout: &EscrowPayout<'_, 'info>,
+    parties: &SettlementParties<'_, 'info>,
+    treasury: AccountInfo<'info>,
+    stats: &mut SettlementStats<'_>,
+    award_bps: u16,
+) -> Result<()> {
+    // A completed quest has nothing left to pay out
+    require!(quest.status != QuestStatus::Completed, QuestError::QuestNotActive);
+
+    // Chain quests release one step's share per approval; the last step takes
+    // whatever is left so rounding never strands funds in escrow
+    let final_step = claim.current_step as usize + 1 >= quest.steps.len();
+    let earned_amount = if final_step {
+        quest.unpaid_reward()?
+    } else {
+        quest.reward_amount
+            .checked_mul(quest.steps[claim.current_step as usize].share_bps as u64)
+            .ok_or(QuestError::Overflow)?
+            .checked_div(10000)
+            .ok_or(QuestError::Overflow)?
+    };
+    let reward_amount = earned_amount
+        .checked_mul(award_bps as u64)
+        .ok_or(QuestError::Overflow)?
+        .checked_div(10000)
+        .ok_or(QuestError::Overflow)?;
+    let refund_amount = earned_amount
+        .checked_sub(reward_amount)
+        .ok_or(QuestError::Overflow)?;
+    let fee_amount = reward_amount
+        .checked_mul(config.fee_basis_points as u64)
+        .ok_or(QuestError::Overflow)?
+        .checked_div(10000)
+        .ok_or(QuestError::Overflow)?;
+    let reward_after_fee = reward_amount
+        .checked_sub(fee_amount)
+        .ok_or(QuestError::Overflow)?;
+    // Native SOL cannot be burned through the token program, so the whole
+    // fee goes to treasury for SOL rewards
+    let burn_amount = if quest.reward_mint == native_mint::ID {
+        0
+    } else {
+        fee_amount
+            .checked_mul(config.burn_basis_points as u64)
+            .ok_or(QuestError::Overflow)?
+            .checked_div(10000)
+            .ok_or(QuestError::Overflow)?
+    };
+    let treasury_amount = fee_amount
+        .checked_sub(burn_amount)
+        .ok_or(QuestError::Overflow)?;
+
+    // A step approved too close to the time limit leaves no window for the
+    // next one, so the chain ends here and the stake goes back with this step
+    let now = Clock::get()?.unix_timestamp;
+    let next_deadline = if final_step {
+        None
+    } else {
+        quest.fresh_proof_deadline(now, &config.deadline_bounds)?
+    };
+    let chain_ends = final_step || next_deadline.is_none();
+
+    // The stake stays in escrow until the chain ends
+    pay_claimers(claim, payout, parties, reward_after_fee, chain_ends)?;
+
+    if refund_amount > 0 {
+        let creator_refund = reserve_contributor_refund(quest, refund_amount)?;
+        payout.pay(
+            parties.creator_token_account,
+            &parties.creator,
+            creator_refund,
+        )?;
+    }
+
+    if treasury_amount > 0 {
+        payout.transfer(treasury, treasury_amount)?;
+    }
+
+    if burn_amount > 0 {
+        payout.burn(burn_amount)?;
+    }
+
+    // Guild members are paid weighted shares, so the leader only earned theirs
+    let claimer_earned = match claim.members.first() {
+        Some(leader) => reward_after_fee
+            .checked_mul(leader.weight_bps as u64)
+            .ok_or(QuestError::Overflow)?
+            .checked_div(10000)
+            .ok_or(QuestError::Overflow)?,
+        None => reward_after_fee,
+    };
+    stats.claimer.total_earned = stats.claimer.total_earned
+        .checked_add(claimer_earned)
+        .ok_or(QuestError::Overflow)?;
+    stats.creator.total_paid_out = stats.creator.total_paid_out
+        .checked_add(reward_amount)
+        .ok_or(QuestError::Overflow)?;
+
+    if !final_step {
+        let step = claim.current_step;
+        quest.steps_paid = quest.steps_paid
+            .checked_add(reward_amount)
+            .ok_or(QuestError::Overflow)?;
+
+        emit!(StepCompleted {
+            quest_id: quest.id,
+            quest: quest.key(),
+            claimer: claim.claimer,
+            step,
+            reward_amount: reward_after_fee,
+            fee_amount,
+            burn_amount,
+        });
+
+        let Some(proof_deadline) = next_deadline else {
+            // Nothing is forfeited; the unpaid steps go back to the creator
+            claim.status = ClaimStatus::Approved;
+            quest.current_claimers = quest.current_claimers.saturating_sub(1);
+            quest.status = QuestStatus::Expired;
+            return close_if_settled(quest, claim, payout, parties);
+        };
+
+        // Back to Active for the next step's proof
+        claim.current_step = step + 1;
+        claim.status = ClaimStatus::Active;
+        stats.profile.active_claims = stats.profile.active_claims
+            .checked_add(1)
+            .ok_or(QuestError::Overflow)?;
+        claim.proof_deadline = proof_deadline;
+        claim.review_deadline = None;
+        claim.oracle_votes.clear();
+        claim.proof_hash = None;
+        claim.submitted_at = None;
+
+        return Ok(());
+    }
+
+    claim.status = ClaimStatus::Approved;
+    stats.claimer.quests_completed = stats.claimer.quests_completed
+        .checked_add(1)
+        .ok_or(QuestError::Overflow)?;
+    quest.current_claimers = quest.current_claimers.saturating_sub(1);
+    if quest.pooled {
+        // Each approval settles one slot; the quest completes once all are paid
+        quest.completed_claimers = quest.completed_claimers
+            .checked_add(1)
+            .ok_or(QuestError::Overflow)?;
+        if quest.completed_claimers >= quest.max_claimers {
+            quest.status = QuestStatus::Completed;
+        }
+    } else {
+        quest.status = QuestStatus::Completed;
+    }
+
+    emit!(QuestCompleted {
+        quest_id: quest.id,
+        quest: quest.key(),
+        claimer: claim.claimer,
+        award_bps,
+        reward_amount: reward_after_fee,
+        fee_amount,
+        burn_amount,
+    });
+
+    close_if_settled(quest, claim, payout, parties)
+}
+
+/// Returns reward and stake per the rejection rules. Shared by the oracle and
+/// creator rejection paths.
+#[allow(clippy::too_many_arguments)]
+fn reject_claim<'info>(
+    config: &QuestConfig,
+    quest: &mut Account<'info, Quest>,
+    claim: &mut Account<'info, Claim>,
+    payout: &EscrowPayout<'_, 'info>,
+    parties: &SettlementParties<'_, 'info>,
+    treasury: AccountInfo<'info>,
+    claimer_stats: &mut UserStats,
+    safety_flagged: bool,
+) -> Result<()> {
+    claimer_stats.claims_rejected = claimer_stats.claims_rejected
+        .checked_add(1)
+        .ok_or(QuestError::Overflow)?;
+
+    if quest.pooled {
+        // Pooled rejection: the reward stays in the pool for the next claimer,
+        // only the stake is settled
+        if claim.stake_amount > 0 {
+            if safety_flagged {
+                pay_claimers(claim, payout, parties, 0, true)?;
+            } else {
+                let creator_share = forfeit_stake(config, quest, claim, payout, treasury)?;
+                payout.pay(
+                    parties.creator_token_account,
+                    &parties.creator,
+                    creator_share,
+                )?;
+            }
+        }
+    } else if safety_flagged {
+        // Safety rejection: reward back to its funders, stake back to claimer
+        let reward_amount = quest.unpaid_reward()?;
+        let creator_refund = reserve_contributor_refund(quest, reward_amount)?;
+        payout.pay(
+            parties.creator_token_account,
+            &parties.creator,
+            creator_refund,
+        )?;
+
+        if claim.stake_amount > 0 {
+            pay_claimers(claim, payout, parties, 0, true)?;
+        }
+    } else {
+        // Normal rejection: reward back to its funders, stake forfeited
+        let stake_share = if claim.stake_amount > 0 {
+            forfeit_stake(config, quest, claim, payout, treasury)?
+        } else {
+            0
+        };
+        let reward_amount = quest.unpaid_reward()?;
+        let creator_payout = reserve_contributor_refund(quest, reward_amount)?
+            .checked_add(stake_share)
+            .ok_or(QuestError::Overflow)?;
+
+        payout.pay(
+            parties.creator_token_account,
+            &parties.creator,
+            creator_payout,
+        )?;
+    }
+
+    claim.status = ClaimStatus::Rejected;
+    quest.current_claimers = quest.current_claimers.saturating_sub(1);
+    if quest.pooled {
+        if quest.status == QuestStatus::Claimed {
+            quest.status = QuestStatus::Active;
+        }
+    } else {
+        quest.status = QuestStatus::Failed;
+    }
+
+    emit!(QuestFailed {
+        quest_id: quest.id,
+        quest: quest.key(),
+        claimer: claim.claimer,
+        reason: FailReason::Rejected,
+    });
+
+    close_if_settled(quest, claim, payout, parties)
+}
+
+/// Once the quest is settled the claim is no longer needed, and the quest and
+/// escrow can go too if no other stakes are left in escrow.
+fn close_if_settled<'info>(
+    quest: &mut Account<'info, Quest>,
+    claim: &Account<'info, Claim>,
+    payout: &EscrowPayout<'_, 'info>,
+    parties: &SettlementParties<'_, 'info>,
+) -> Result<()> {
+    if !quest.is_settled() {
+        return Ok(());
+    }
+
+    claim.close(parties.claimer.clone())?;
+    if quest.current_claimers == 0 {
+        close_quest_accounts(
+            quest,
+            payout,
+            parties.creator.clone(),
+            parties.creator_token_account,
+            None,
+        )?;
+    }
+
+    Ok(())
+}
+
+// ============================================================================
+// STATE
+// ============================================================================
+
+#[account]
+#[derive(InitSpace)]
+pub struct QuestConfig {
+    /// Admin key: changes protocol settings and manages the oracle set
+    pub authority: Pubkey,
+    pub pending_authority: Option<Pubkey>,
+    /// Owner of the treasury token accounts, one per reward mint
+    pub treasury: Pubkey,
+    pub fee_basis_points: u16,
+    pub burn_basis_points: u16,
+    pub dispute: DisputeSettings,
+    pub deadline_bounds: DeadlineBounds,
+    pub rate_limits: RateLimits,
+    pub forfeit_split: ForfeitSplit,
+    pub pending_update: Option<PendingConfigUpdate>,
+    pub bump: u8,
+}
+
+/// Config fields to change; `None` leaves the current value in place
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct ConfigUpdate {
+    pub treasury: Option<Pubkey>,
+    pub fee_basis_points: Option<u16>,
+    pub burn_basis_points: Option<u16>,
+    pub dispute: Option<DisputeSettings>,
+    pub deadline_bounds: Option<DeadlineBounds>,
+    pub rate_limits: Option<RateLimits>,
+    pub forfeit_split: Option<ForfeitSplit>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct DisputeSettings {
+    /// How long a claimer has to dispute a rejection
+    pub window_seconds: i64,
+    /// How long jurors have to vote once a dispute is opened
+    pub voting_seconds: i64,
+    /// Posted by the claimer, forfeited to the majority jurors if the rejection stands
+    pub bond_lamports: u64,
+}
+
+impl DisputeSettings {
+    pub fn is_valid(&self) -> bool {
+        self.window_seconds > 0 && self.voting_seconds > 0
+    }
+}
+
+/// Range creators may pick per-quest proof and review deadlines from, in hours
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct DeadlineBounds {
+    pub min_proof_hours: u8,
+    pub max_proof_hours: u8,
+    pub min_review_hours: u8,
+    pub max_review_hours: u8,
+}
+
+impl DeadlineBounds {
+    pub fn is_valid(&self) -> bool {
+        self.min_proof_hours > 0
+            && self.min_proof_hours <= self.max_proof_hours
+            && self.min_review_hours > 0
+            && self.min_review_hours <= self.max_review_hours
+    }
+}
+
+/// Per-wallet anti-abuse limits, enforced through UserProfile
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct RateLimits {
+    pub max_quests_per_day: u16,
+    /// Claims not yet submitted, abandoned or expired
+    pub max_active_claims: u8,
+    /// Expired or abandoned claims within the window that block new claims
+    pub max_recent_failures: u8,
+    pub failure_window_seconds: i64,
+}
+
+impl RateLimits {
+    pub fn is_valid(&self) -> bool {
+        self.max_quests_per_day > 0
+            && self.max_active_claims > 0
+            && self.max_recent_failures > 0
+            && self.max_recent_failures as usize <= MAX_TRACKED_FAILURES
+            && self.failure_window_seconds > 0
+    }
+}
+
+/// Where a forfeited claim stake goes, in basis points summing to 10000
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct ForfeitSplit {
+    pub creator_bps: u16,
+    pub treasury_bps: u16,
+    pub burn_bps: u16,
+}
+
+impl ForfeitSplit {
+    pub fn is_valid(&self) -> bool {
+        self.creator_bps as u32 + self.treasury_bps as u32 + self.burn_bps as u32 == 10000
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct PendingConfigUpdate {
+    pub update: ConfigUpdate,
+    pub effective_at: i64,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Quest {
+    /// Creator's nonce; the quest PDA is [b"quest", creator, id]
+    pub id: u64,
+    pub creator: Pubkey,
+    pub escrow: Pubkey,
+    pub reward_mint: Pubkey,
+    pub reward_amount: u64,
+    pub quest_type: QuestType,
+    pub status: QuestStatus,
+    pub target: Option<Pubkey>,
+    pub max_claimers: u8,
+    pub current_claimers: u8,
+    pub pooled: bool,
+    pub completed_claimers: u8,
+    pub time_limit: Option<i64>,
+    pub proof_deadline_hours: u8,
+    pub review_deadline_hours: u8,
+    pub description_hash: [u8; 32],
+    /// Ordered steps of a chain quest; empty for a single-proof quest
+    #[max_len(MAX_QUEST_STEPS)]
+    pub steps: Vec<QuestStep>,
+    /// Reward already released for completed chain steps
+    pub steps_paid: u64,
+    /// What the creator put into escrow; the basis for pro-rata refunds
+    pub creator_funded: u64,
+    /// Third-party top-ups still owed a refund_contribution
+    pub contributed_amount: u64,
+    pub contributors: u16,
+    /// Returned reward held in escrow for contributors
+    pub contributor_refund_pool: u64,
+    /// Set once the quest is settled and the refund pool is final
+    pub refunds_ready: bool,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+impl Quest {
+    pub fn min_stake(&self) -> Result<u64> {
+        Ok(self.reward_amount
+            .checked_mul(MIN_STAKE_BPS)
+            .ok_or(QuestError::Overflow)?
+            .checked_div(10000)
+            .ok_or(QuestError::Overflow)?)
+    }
+
+    /// Reward still held for the claimer; less than reward_amount once chain
+    /// steps have paid out
+    pub fn unpaid_reward(&self) -> Result<u64> {
+        Ok(self.reward_amount
+            .checked_sub(self.steps_paid)
+            .ok_or(QuestError::Overflow)?)
+    }
+
+    /// Proof window starting at `now`; never runs past the time limit
+    pub fn proof_deadline_from(&self, now: i64) -> Result<i64> {
+        let deadline = now
+            .checked_add(
+                (self.proof_deadline_hours as i64)
+                    .checked_mul(SECONDS_PER_HOUR)
+                    .ok_or(QuestError::Overflow)?
+            )
+            .ok_or(QuestError::Overflow)?;
+        Ok(match self.time_limit {
+            Some(limit) => deadline.min(limit),
+            None => deadline,
+        })
+    }
+
+    /// Fresh proof window for a claim already under way, or None when the
+    /// time limit leaves less than the shortest window creators may pick
+    pub fn fresh_proof_deadline(&self, now: i64, bounds: &DeadlineBounds) -> Result<Option<i64>> {
+        let deadline = self.proof_deadline_from(now)?;
+        let min_window = (bounds.min_proof_hours as i64)
+            .checked_mul(SECONDS_PER_HOUR)
+            .ok_or(QuestError::Overflow)?;
+        Ok((deadline.saturating_sub(now) >= min_window).then_some(deadline))
+    }
+
+    pub fn is_settled(&self) -> bool {
+        matches!(
+            self.status,
+            QuestStatus::Completed
+                | QuestStatus::Failed
+                | QuestStatus::Cancelled
+                | QuestStatus::Expired
+        )
+    }
+}
+
+/// Per-wallet counters behind the rate limits
+#[account]
+#[derive(InitSpace)]
+pub struct UserProfile {
+    pub wallet: Pubkey,
+    /// Start of the current 24h quest creation window
+    pub quest_window_start: i64,
+    pub quests_in_window: u16,
+    /// Quests created in the 24h before the current window
+    pub quests_in_last_window: u16,
+    /// Claims still waiting on a proof: taken, sent back for revision or
+    /// moved on to the next chain step
+    pub active_claims: u8,
+    /// When recent claims expired or were abandoned, oldest first
+    #[max_len(MAX_TRACKED_FAILURES)]
+    pub recent_failures: Vec<i64>,
+    /// Safety-flagged rejections and manual flags
+    pub strikes: u8,
+    pub banned: bool,
+    pub bump: u8,
+}
+
+impl UserProfile {
+    pub fn init_if_new(&mut self, wallet: Pubkey, bump: u8) {
+        if self.wallet == Pubkey::default() {
+            self.wallet = wallet;
+            self.bump = bump;
+        }
+    }
 
-    #[account(mut, seeds = [b"escrow", quest.key().as_ref()], bump)]
-    pub escrow: Account<'info, TokenAccount>,
+    /// Counts a new quest against the daily limit over a sliding 24h window.
+    /// The last window's count is weighted by how much of it still overlaps
+    /// the past day, so the limit can't be doubled across a window boundary.
+    pub fn record_quest(&mut self, now: i64, max_per_day: u16) -> Result<()> {
+        let mut elapsed = now.saturating_sub(self.quest_window_start);
+        if elapsed >= 2 * SECONDS_PER_DAY {
+            self.quest_window_start = now;
+            self.quests_in_last_window = 0;
+            self.quests_in_window = 0;
+            elapsed = 0;
+        } else if elapsed >= SECONDS_PER_DAY {
+            self.quest_window_start += SECONDS_PER_DAY;
+            self.quests_in_last_window = self.quests_in_window;
+            self.quests_in_window = 0;
+            elapsed -= SECONDS_PER_DAY;
+        }
 
-    #[account(mut)]
-    pub claimer_token_account: Account<'info, TokenAccount>,
+        // Rounded up so a partly overlapping quest still counts
+        let overlap = SECONDS_PER_DAY - elapsed;
+        let carried =
+            (self.quests_in_last_window as i64 * overlap + SECONDS_PER_DAY - 1) / SECONDS_PER_DAY;
+        require!(
+            carried + (self.quests_in_window as i64) < max_per_day as i64,
+            QuestError::DailyQuestLimitReached
+        );
+        self.quests_in_window += 1;
+        Ok(())
+    }
 
-    #[account(mut)]
-    pub treasury: Account<'info, TokenAccount>,
+    pub fn record_failure(&mut self, now: i64) {
+        self.active_claims = self.active_claims.saturating_sub(1);
+        if self.recent_failures.len() >= MAX_TRACKED_FAILURES {
+            self.recent_failures.remove(0);
+        }
+        self.recent_failures.push(now);
+    }
 
-    /// CHECK: Anyone can call this (permissionless crank)
-    pub cranker: Signer<'info>,
-    pub token_program: Program<'info, Token>,
-}
+    /// Bans the wallet once it reaches MAX_STRIKES
+    pub fn add_strike(&mut self) {
+        self.strikes = self.strikes.saturating_add(1);
+        if self.strikes >= MAX_STRIKES {
+            self.banned = true;
+        }
 
-#[derive(Accounts)]
-pub struct CancelQuest<'info> {
-    #[account(mut)]
-    pub quest: Account<'info, Quest>,
+        emit!(StrikeRecorded {
+            wallet: self.wallet,
+            strikes: self.strikes,
+            banned: self.banned,
+        });
+    }
 
-    #[account(mut, seeds = [b"escrow", quest.key().as_ref()], bump)]
-    pub escrow: Account<'info, TokenAccount>,
+    pub fn recent_failures(&self, now: i64, window_seconds: i64) -> u8 {
+        self.recent_failures
+            .iter()
+            .filter(|at| now.saturating_sub(**at) < window_seconds)
+            .count() as u8
+    }
+}
 
-    #[account(mut)]
-    pub creator: Signer<'info>,
+/// Public reputation record, updated by every settlement path
+#[account]
+#[derive(InitSpace)]
+pub struct UserStats {
+    pub wallet: Pubkey,
+    pub quests_posted: u32,
+    pub quests_completed: u32,
+    pub claims_rejected: u32,
+    pub claims_abandoned: u32,
+    pub claims_expired: u32,
+    /// Rewards received, net of protocol fees
+    pub total_earned: u64,
+    /// Rewards paid to claimers of this wallet's quests, fees included
+    pub total_paid_out: u64,
+    pub bump: u8,
+}
 
-    #[account(mut, constraint = creator_token_account.owner == creator.key())]
-    pub creator_token_account: Account<'info, TokenAccount>,
+impl UserStats {
+    pub fn init_if_new(&mut self, wallet: Pubkey, bump: u8) {
+        if self.wallet == Pubkey::default() {
+            self.wallet = wallet;
+            self.bump = bump;
+        }
+    }
+}
 
-    pub token_program: Program<'info, Token>,
+/// Per-creator nonce for quest PDAs, so creators don't contend on one account
+#[account]
+#[derive(InitSpace)]
+pub struct QuestCounter {
+    pub creator: Pubkey,
+    pub next_nonce: u64,
+    pub bump: u8,
 }
 
-// ============================================================================
-// STATE
-// ============================================================================
+impl QuestCounter {
+    pub fn init_if_new(&mut self, creator: Pubkey, bump: u8) {
+        if self.creator == Pubkey::default() {
+            self.creator = creator;
+            self.bump = bump;
+        }
+    }
+}
 
+/// Protocol-wide counters, kept off the quest creation hot path
 #[account]
 #[derive(InitSpace)]
-pub struct QuestConfig {
-    pub authority: Pubkey,
-    pub treasury: Pubkey,
-    pub fee_basis_points: u16,
-    pub burn_basis_points: u16,
+pub struct GlobalStats {
     pub quest_count: u64,
     pub bump: u8,
 }
 
+/// Full quest text, checked against the quest's description_hash
 #[account]
 #[derive(InitSpace)]
-pub struct Quest {
-    pub id: u64,
+pub struct QuestDescription {
+    pub quest: Pubkey,
+    /// Paid the rent, so gets it back when the description is closed
     pub creator: Pubkey,
-    pub escrow: Pubkey,
-    pub reward_mint: Pubkey,
-    pub reward_amount: u64,
-    pub quest_type: QuestType,
-    pub status: QuestStatus,
-    pub target: Option<Pubkey>,
-    pub max_claimers: u8,
-    pub current_claimers: u8,
-    pub time_limit: Option<i64>,
-    pub proof_deadline_hours: u8,
-    pub review_deadline_hours: u8,
-    pub description_hash: [u8; 32],
-    pub created_at: i64,
+    #[max_len(MAX_DESCRIPTION_LEN)]
+    pub text: String,
+    pub bump: u8,
+}
+
+/// A third party's top-up to a quest reward
+#[account]
+#[derive(InitSpace)]
+pub struct Contribution {
+    pub quest: Pubkey,
+    pub contributor: Pubkey,
+    pub amount: u64,
     pub bump: u8,
 }
 
@@ -854,12 +4504,140 @@ pub struct Claim {
     pub status: ClaimStatus,
     pub proof_deadline: i64,
     pub review_deadline: Option<i64>,
+    pub dispute_deadline: Option<i64>,
+    pub safety_flagged: bool,
+    #[max_len(MAX_ORACLES)]
+    pub oracle_votes: Vec<OracleVote>,
+    /// Attestations accepted so far; the next one must carry this plus one
+    pub attestation_nonce: u64,
     pub proof_hash: Option<[u8; 32]>,
     pub claimed_at: i64,
     pub submitted_at: Option<i64>,
+    /// Chain quest step the next proof is for
+    pub current_step: u8,
+    /// Proof resubmissions requested so far, capped at MAX_REVISIONS
+    pub revisions: u8,
+    /// Guild members, leader first; empty for a solo claim
+    #[max_len(MAX_GUILD_MEMBERS)]
+    pub members: Vec<GuildMember>,
+    pub bump: u8,
+}
+
+impl Claim {
+    pub fn is_settled(&self) -> bool {
+        matches!(
+            self.status,
+            ClaimStatus::Approved
+                | ClaimStatus::Rejected
+                | ClaimStatus::Abandoned
+                | ClaimStatus::Expired
+        )
+    }
+}
+
+/// Commitment to the proof currently submitted for a claim
+#[account]
+#[derive(InitSpace)]
+pub struct Proof {
+    pub claim: Pubkey,
+    pub claimer: Pubkey,
+    pub step: u8,
+    pub proof_hash: [u8; 32],
+    /// Storage location of the video (R2 or Arweave)
+    #[max_len(MAX_PROOF_URI_LEN)]
+    pub uri: String,
+    pub duration_seconds: u32,
+    /// Merkle root over the per-second frame hashes the oracle judged
+    pub frame_root: [u8; 32],
+    pub submitted_at: i64,
+    pub bump: u8,
+}
+
+/// A guild member's split as requested by the leader
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct GuildShare {
+    pub wallet: Pubkey,
+    /// Share of the stake owed and the reward paid
+    pub weight_bps: u16,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct GuildMember {
+    pub wallet: Pubkey,
+    pub weight_bps: u16,
+    pub stake: u64,
+    pub joined: bool,
+}
+
+/// One stage of a chain quest
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct QuestStep {
+    pub description_hash: [u8; 32],
+    /// Share of the reward released when this step is approved
+    pub share_bps: u16,
+}
+
+/// Keys allowed to vote on approve/reject; cannot touch settings
+#[account]
+#[derive(InitSpace)]
+pub struct OracleSet {
+    #[max_len(MAX_ORACLES)]
+    pub oracles: Vec<Pubkey>,
+    /// Matching votes needed to settle a claim
+    pub threshold: u8,
+    pub bump: u8,
+}
+
+/// Oracle verdict that anyone can relay. The signed message is the domain
+/// tag and program id followed by the borsh encoding of this struct.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct Attestation {
+    pub claim: Pubkey,
+    /// Proof the verdict was reached on; a resubmitted proof needs a new one
+    pub proof_hash: [u8; 32],
+    pub decision: AttestationDecision,
+    /// Verifier confidence, 0-100
+    pub confidence: u8,
+    /// Bit per safety category; any set bit spares the claimer's stake on reject
+    pub safety_flags: u32,
+    /// One past the claim's accepted attestation count
+    pub nonce: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct OracleVote {
+    pub oracle: Pubkey,
+    pub verdict: OracleVerdict,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct JurorSet {
+    #[max_len(MAX_JURORS)]
+    pub jurors: Vec<Pubkey>,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Dispute {
+    pub claim: Pubkey,
+    pub claimer: Pubkey,
+    pub bond: u64,
+    pub voting_ends_at: i64,
+    pub votes_for_claimer: u8,
+    pub votes_for_creator: u8,
+    #[max_len(MAX_JURORS)]
+    pub votes: Vec<JurorVote>,
     pub bump: u8,
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct JurorVote {
+    pub juror: Pubkey,
+    pub for_claimer: bool,
+}
+
 // ============================================================================
 // ENUMS
 // ============================================================================
@@ -888,6 +4666,31 @@ pub enum ClaimStatus {
     Rejected,
     Abandoned,
     Expired,
+    /// Rejected, waiting out the dispute window
+    RejectionPending,
+    Disputed,
+    /// Guild claim waiting for members to post their stakes
+    Forming,
+    /// Proof sent back; the claimer may resubmit before the new deadline
+    NeedsRevision,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum OracleVerdict {
+    Approve,
+    Reject,
+    /// Reject without slashing the claimer's stake
+    SafetyReject,
+    /// Pay this share of the reward and return the stake
+    Partial { bps: u16 },
+    /// Send the proof back for resubmission
+    Revise,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum AttestationDecision {
+    Approve,
+    Reject,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
@@ -904,22 +4707,116 @@ pub enum FailReason {
 #[event]
 pub struct ProtocolInitialized {
     pub authority: Pubkey,
+    pub oracle: Pubkey,
     pub treasury: Pubkey,
     pub fee_basis_points: u16,
 }
 
+#[event]
+pub struct ConfigUpdateQueued {
+    pub update: ConfigUpdate,
+    pub effective_at: i64,
+}
+
+#[event]
+pub struct ConfigUpdated {
+    pub treasury: Pubkey,
+    pub fee_basis_points: u16,
+    pub burn_basis_points: u16,
+    pub dispute: DisputeSettings,
+    pub deadline_bounds: DeadlineBounds,
+    pub rate_limits: RateLimits,
+    pub forfeit_split: ForfeitSplit,
+}
+
+#[event]
+pub struct AuthorityProposed {
+    pub authority: Pubkey,
+    pub pending_authority: Pubkey,
+}
+
+#[event]
+pub struct AuthorityTransferred {
+    pub previous_authority: Pubkey,
+    pub new_authority: Pubkey,
+}
+
+#[event]
+pub struct OracleAdded {
+    pub oracle: Pubkey,
+    pub threshold: u8,
+}
+
+#[event]
+pub struct OracleRemoved {
+    pub oracle: Pubkey,
+    pub threshold: u8,
+}
+
+#[event]
+pub struct OracleThresholdSet {
+    pub threshold: u8,
+}
+
+#[event]
+pub struct OracleVoteCast {
+    pub quest_id: u64,
+    pub quest: Pubkey,
+    pub claimer: Pubkey,
+    pub oracle: Pubkey,
+    pub verdict: OracleVerdict,
+    pub votes: u8,
+    pub threshold: u8,
+}
+
+#[event]
+pub struct AttestationRelayed {
+    pub quest_id: u64,
+    pub quest: Pubkey,
+    pub claimer: Pubkey,
+    pub decision: AttestationDecision,
+    pub confidence: u8,
+    pub safety_flags: u32,
+    pub nonce: u64,
+}
+
+#[event]
+pub struct JurorAdded {
+    pub juror: Pubkey,
+}
+
+#[event]
+pub struct JurorRemoved {
+    pub juror: Pubkey,
+}
+
+#[event]
+pub struct StrikeRecorded {
+    pub wallet: Pubkey,
+    pub strikes: u8,
+    pub banned: bool,
+}
+
+#[event]
+pub struct AccountUnbanned {
+    pub wallet: Pubkey,
+}
+
 #[event]
 pub struct QuestCreated {
     pub quest_id: u64,
+    pub quest: Pubkey,
     pub creator: Pubkey,
     pub reward_amount: u64,
     pub reward_mint: Pubkey,
     pub quest_type: QuestType,
+    pub pooled: bool,
 }
 
 #[event]
 pub struct QuestClaimed {
     pub quest_id: u64,
+    pub quest: Pubkey,
     pub claimer: Pubkey,
     pub stake_amount: u64,
 }
@@ -927,40 +4824,163 @@ pub struct QuestClaimed {
 #[event]
 pub struct ProofSubmitted {
     pub quest_id: u64,
+    pub quest: Pubkey,
     pub claimer: Pubkey,
+    pub step: u8,
     pub proof_hash: [u8; 32],
+    pub uri: String,
+    pub duration_seconds: u32,
+    pub frame_root: [u8; 32],
+}
+
+#[event]
+pub struct RevisionRequested {
+    pub quest_id: u64,
+    pub quest: Pubkey,
+    pub claimer: Pubkey,
+    pub step: u8,
+    pub revision: u8,
+    pub proof_deadline: i64,
+}
+
+#[event]
+pub struct StakeForfeited {
+    pub quest_id: u64,
+    pub quest: Pubkey,
+    pub claimer: Pubkey,
+    pub creator_amount: u64,
+    pub treasury_amount: u64,
+    pub burn_amount: u64,
+}
+
+#[event]
+pub struct GuildMemberJoined {
+    pub quest_id: u64,
+    pub quest: Pubkey,
+    pub claimer: Pubkey,
+    pub member: Pubkey,
+    pub stake_amount: u64,
+}
+
+#[event]
+pub struct StepCompleted {
+    pub quest_id: u64,
+    pub quest: Pubkey,
+    pub claimer: Pubkey,
+    pub step: u8,
+    pub reward_amount: u64,
+    pub fee_amount: u64,
+    pub burn_amount: u64,
 }
 
 #[event]
 pub struct QuestCompleted {
     pub quest_id: u64,
+    pub quest: Pubkey,
     pub claimer: Pubkey,
+    /// Share of the reward awarded; below 10000 for partial credit
+    pub award_bps: u16,
     pub reward_amount: u64,
     pub fee_amount: u64,
+    pub burn_amount: u64,
 }
 
 #[event]
 pub struct QuestFailed {
     pub quest_id: u64,
+    pub quest: Pubkey,
     pub claimer: Pubkey,
     pub reason: FailReason,
 }
 
+#[event]
+pub struct ClaimRejected {
+    pub quest_id: u64,
+    pub quest: Pubkey,
+    pub claimer: Pubkey,
+    pub safety_flagged: bool,
+    pub dispute_deadline: i64,
+}
+
+#[event]
+pub struct DisputeOpened {
+    pub quest_id: u64,
+    pub quest: Pubkey,
+    pub claimer: Pubkey,
+    pub bond: u64,
+    pub voting_ends_at: i64,
+}
+
+#[event]
+pub struct VoteCast {
+    pub claim: Pubkey,
+    pub juror: Pubkey,
+    pub for_claimer: bool,
+}
+
+#[event]
+pub struct DisputeResolved {
+    pub quest_id: u64,
+    pub quest: Pubkey,
+    pub claimer: Pubkey,
+    pub claimer_wins: bool,
+    pub votes_for_claimer: u8,
+    pub votes_for_creator: u8,
+}
+
 #[event]
 pub struct QuestCancelled {
     pub quest_id: u64,
+    pub quest: Pubkey,
+    pub creator: Pubkey,
+}
+
+#[event]
+pub struct RewardIncreased {
+    pub quest_id: u64,
+    pub quest: Pubkey,
+    pub contributor: Pubkey,
+    pub amount: u64,
+    /// New per-claimer reward
+    pub reward_amount: u64,
+}
+
+#[event]
+pub struct ContributionRefunded {
+    pub quest_id: u64,
+    pub quest: Pubkey,
+    pub contributor: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct QuestExpired {
+    pub quest_id: u64,
+    pub quest: Pubkey,
+    /// Reward returned to the creator by the expiry itself
+    pub refunded: u64,
+    /// Claims still in flight; the last one to settle closes the quest
+    pub live_claims: u8,
+}
+
+#[event]
+pub struct QuestClosed {
+    pub quest_id: u64,
+    pub quest: Pubkey,
     pub creator: Pubkey,
 }
 
 #[event]
 pub struct ClaimAbandoned {
     pub quest_id: u64,
+    pub quest: Pubkey,
     pub claimer: Pubkey,
 }
 
 #[event]
 pub struct ClaimExpired {
     pub quest_id: u64,
+    pub quest: Pubkey,
     pub claimer: Pubkey,
 }
 
@@ -980,6 +5000,62 @@ pub enum QuestError {
     QuestFull,
     #[msg("Quest has expired")]
     QuestExpired,
+    #[msg("Quest has no time limit")]
+    NoTimeLimit,
+    #[msg("Daily quest creation limit reached")]
+    DailyQuestLimitReached,
+    #[msg("Too many claims awaiting proof")]
+    TooManyActiveClaims,
+    #[msg("Too many recently expired or abandoned claims")]
+    TooManyRecentFailures,
+    #[msg("Invalid rate limits")]
+    InvalidRateLimits,
+    #[msg("Forfeit split must sum to 100%")]
+    InvalidForfeitSplit,
+    #[msg("Account is banned")]
+    AccountBanned,
+    #[msg("Chain quests need 2-8 steps whose shares sum to 100%, one claimer and no pool")]
+    InvalidQuestSteps,
+    #[msg("Guilds need 2-8 distinct members led by the claimer, with weights summing to 100%")]
+    InvalidGuild,
+    #[msg("Guild is not waiting for members")]
+    GuildNotForming,
+    #[msg("Not a member of this guild")]
+    NotGuildMember,
+    #[msg("Already joined this guild")]
+    AlreadyJoinedGuild,
+    #[msg("Guild member accounts do not match the claim")]
+    GuildAccountsMismatch,
+    #[msg("Partial award must be between 0 and 100% exclusive")]
+    InvalidAwardBps,
+    #[msg("Chain quest steps cannot be partially approved")]
+    PartialChainStep,
+    #[msg("Claim has used all its proof revisions")]
+    RevisionLimitReached,
+    #[msg("Time limit leaves no room for a fresh proof window")]
+    NoProofWindowLeft,
+    #[msg("Proof URI must be 1-200 bytes")]
+    InvalidProofUri,
+    #[msg("Proof duration must be positive")]
+    InvalidProofDuration,
+    #[msg("Description must be at most 280 bytes")]
+    DescriptionTooLong,
+    #[msg("Description does not hash to description_hash")]
+    DescriptionHashMismatch,
+    #[msg("Description text and account must be passed together")]
+    DescriptionMismatch,
+    #[msg("Description account bump not found")]
+    MissingDescriptionBump,
+    #[msg("Treasury account must belong to the configured treasury and hold the reward mint")]
+    InvalidTreasury,
+    #[msg("Contribution is too small to raise the reward")]
+    ContributionTooLow,
+    #[msg("Quest creator cannot contribute to their own quest")]
+    CreatorCannotContribute,
+    #[msg("Proof or review deadline is outside the protocol bounds")]
+    InvalidDeadline,
+    #[msg("Invalid deadline bounds")]
+    InvalidDeadlineBounds,
     #[msg("Not the target user for this quest")]
     NotTargetUser,
     #[msg("Claim is not active")]
@@ -1012,6 +5088,60 @@ pub enum QuestError {
     InvalidTimeLimit,
     #[msg("Invalid fee configuration")]
     InvalidFeeConfig,
+    #[msg("Pooled rewards are only available for open quests")]
+    PooledQuestMustBeOpen,
+    #[msg("Token account required for SPL rewards")]
+    MissingTokenAccount,
+    #[msg("Not the protocol authority")]
+    NotAuthority,
+    #[msg("No config update queued")]
+    NoPendingConfigUpdate,
+    #[msg("Config update timelock has not elapsed")]
+    TimelockNotElapsed,
+    #[msg("Not the pending authority")]
+    NotPendingAuthority,
+    #[msg("Quest is not settled")]
+    QuestNotSettled,
+    #[msg("Claim is not settled")]
+    ClaimNotSettled,
+    #[msg("Quest still has live claims")]
+    LiveClaimsRemaining,
+    #[msg("Review deadline has passed")]
+    ReviewDeadlinePassed,
+    #[msg("Invalid dispute settings")]
+    InvalidDisputeSettings,
+    #[msg("Already an oracle")]
+    AlreadyOracle,
+    #[msg("Oracle set is full")]
+    OracleSetFull,
+    #[msg("Oracle threshold must be between 1 and the number of oracles")]
+    InvalidOracleThreshold,
+    #[msg("Expected an Ed25519 signature instruction before this one")]
+    MissingAttestation,
+    #[msg("Ed25519 instruction is malformed")]
+    InvalidAttestation,
+    #[msg("Attestation does not match this claim, proof or decision")]
+    AttestationMismatch,
+    #[msg("Attestation nonce is not the claim's next nonce")]
+    StaleAttestationNonce,
+    #[msg("Already a juror")]
+    AlreadyJuror,
+    #[msg("Juror set is full")]
+    JurorSetFull,
+    #[msg("Not a registered juror")]
+    NotJuror,
+    #[msg("Claim has no pending rejection")]
+    NoPendingRejection,
+    #[msg("Dispute window has closed")]
+    DisputeWindowClosed,
+    #[msg("Claim is not disputed")]
+    ClaimNotDisputed,
+    #[msg("Voting has closed")]
+    VotingClosed,
+    #[msg("Juror already voted")]
+    AlreadyVoted,
+    #[msg("Juror accounts do not match the majority voters")]
+    JurorAccountsMismatch,
     #[msg("Arithmetic overflow")]
     Overflow,
 }
@@ -5,6 +5,10 @@ import {
   PublicKey,
   SystemProgram,
   LAMPORTS_PER_SOL,
+  Transaction,
+  sendAndConfirmTransaction,
+  Ed25519Program,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
 } from "@solana/web3.js";
 import {
   TOKEN_PROGRAM_ID,
@@ -12,8 +16,16 @@ import {
   createAccount,
   mintTo,
   getAccount,
+  getMint,
+  NATIVE_MINT,
+  TOKEN_2022_PROGRAM_ID,
+  ExtensionType,
+  getMintLen,
+  createInitializeMintInstruction,
+  createInitializeTransferFeeConfigInstruction,
 } from "@solana/spl-token";
 import { expect } from "chai";
+import { createHash } from "crypto";
 import { Quest } from "../target/types/quest";
 
 const DECIMALS = 6;
@@ -21,6 +33,12 @@ const ONE_TOKEN = 1_000_000;
 const REWARD = 100 * ONE_TOKEN;
 const MIN_STAKE_BPS = 500; // 5%
 const SECONDS_PER_HOUR = 3600;
+// short enough to wait out on a local validator
+const DISPUTE_WINDOW = 4;
+const VOTING_SECONDS = 4;
+const DISPUTE_BOND = LAMPORTS_PER_SOL / 100;
+const PROOF_HOURS = 24;
+const REVIEW_HOURS = 24;
 
 function descHash(text: string): number[] {
   const hash = Array(32).fill(0);
@@ -34,6 +52,36 @@ function proofHash(text: string): number[] {
   return descHash(text);
 }
 
+const PROOF_URI = "ar://bNbA3TEQVL60xlgCcqdz4ZPHFZ711cZ3hmkpGttDt_U";
+const PROOF_SECONDS = 42;
+const FRAME_ROOT = descHash("frame merkle root");
+
+const ATTESTATION_DOMAIN = Buffer.from("quest-attestation-v1");
+
+// domain tag and program id, then the borsh layout of the Attestation struct
+function encodeAttestation(a: {
+  programId: PublicKey;
+  claim: PublicKey;
+  proofHash: number[];
+  approve: boolean;
+  confidence: number;
+  safetyFlags: number;
+  nonce: BN;
+}): Buffer {
+  const buf = Buffer.alloc(32 + 32 + 1 + 1 + 4 + 8);
+  a.claim.toBuffer().copy(buf, 0);
+  Buffer.from(a.proofHash).copy(buf, 32);
+  buf.writeUInt8(a.approve ? 0 : 1, 64);
+  buf.writeUInt8(a.confidence, 65);
+  buf.writeUInt32LE(a.safetyFlags, 66);
+  a.nonce.toArrayLike(Buffer, "le", 8).copy(buf, 70);
+  return Buffer.concat([ATTESTATION_DOMAIN, a.programId.toBuffer(), buf]);
+}
+
+function sleep(ms: number): Promise<void> {
+  return new Promise((resolve) => setTimeout(resolve, ms));
+}
+
 describe("quest", () => {
   const provider = anchor.AnchorProvider.env();
   anchor.setProvider(provider);
@@ -55,16 +103,58 @@ describe("quest", () => {
   // pdas
   let configPda: PublicKey;
   let configBump: number;
+  let jurorSetPda: PublicKey;
+  let oracleSetPda: PublicKey;
+  let globalStatsPda: PublicKey;
 
   const feeBps = 250; // 2.5%
-  const burnBps = 0;
+  const burnBps = 5000; // 50% of fee
+  const disputeSettings = {
+    windowSeconds: new BN(DISPUTE_WINDOW),
+    votingSeconds: new BN(VOTING_SECONDS),
+    bondLamports: new BN(DISPUTE_BOND),
+  };
+  const deadlineBounds = {
+    minProofHours: 1,
+    maxProofHours: 168, // one week
+    minReviewHours: 1,
+    maxReviewHours: 168,
+  };
+  const rateLimits = {
+    maxQuestsPerDay: 500,
+    maxActiveClaims: 20,
+    maxRecentFailures: 3,
+    failureWindowSeconds: new BN(7 * 24 * 3600),
+  };
+  // forfeited stakes: 60% creator, 30% treasury, 10% burned
+  const forfeitSplit = { creatorBps: 6000, treasuryBps: 3000, burnBps: 1000 };
+
+  function creatorForfeitShare(stake: number): number {
+    const toTreasury = Math.floor((stake * forfeitSplit.treasuryBps) / 10000);
+    const burned = Math.floor((stake * forfeitSplit.burnBps) / 10000);
+    return stake - toTreasury - burned;
+  }
 
-  // track quest count for PDA derivation
+  // track the creator's quest nonce for PDA derivation
   let questCount = 0;
 
-  function deriveQuestPda(id: number): [PublicKey, number] {
+  function deriveQuestPda(
+    id: number,
+    creatorKey: PublicKey = creator.publicKey
+  ): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("quest"),
+        creatorKey.toBuffer(),
+        new BN(id).toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId
+    );
+  }
+
+  function deriveQuestCounterPda(creatorKey: PublicKey): [PublicKey, number] {
     return PublicKey.findProgramAddressSync(
-      [Buffer.from("quest"), new BN(id).toArrayLike(Buffer, "le", 8)],
+      [Buffer.from("quest_counter"), creatorKey.toBuffer()],
       program.programId
     );
   }
@@ -76,6 +166,13 @@ describe("quest", () => {
     );
   }
 
+  function deriveUnwrapPda(questPda: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("unwrap"), questPda.toBuffer()],
+      program.programId
+    );
+  }
+
   function deriveClaimPda(
     questPda: PublicKey,
     claimerKey: PublicKey
@@ -86,44 +183,84 @@ describe("quest", () => {
     );
   }
 
+  function deriveProofPda(claimPda: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("proof"), claimPda.toBuffer()],
+      program.programId
+    );
+  }
+
+  function deriveProfilePda(wallet: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("profile"), wallet.toBuffer()],
+      program.programId
+    );
+  }
+
+  function deriveStatsPda(wallet: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("stats"), wallet.toBuffer()],
+      program.programId
+    );
+  }
+
   async function airdrop(pubkey: PublicKey, sol: number = 10) {
     const sig = await connection.requestAirdrop(pubkey, sol * LAMPORTS_PER_SOL);
     await connection.confirmTransaction(sig, "confirmed");
   }
 
-  async function getTokenBalance(ata: PublicKey): Promise<number> {
-    const info = await getAccount(connection, ata);
+  async function getTokenBalance(
+    ata: PublicKey,
+    tokenProgram: PublicKey = TOKEN_PROGRAM_ID
+  ): Promise<number> {
+    const info = await getAccount(connection, ata, undefined, tokenProgram);
     return Number(info.amount);
   }
 
-  // helper: full lifecycle to get a quest into "Submitted" state
-  async function createAndSubmitQuest(): Promise<{
-    questPda: PublicKey;
-    escrowPda: PublicKey;
-    claimPda: PublicKey;
-    questId: number;
-  }> {
+  async function isClosed(pubkey: PublicKey): Promise<boolean> {
+    return (await connection.getAccountInfo(pubkey)) === null;
+  }
+
+  // helper: create a quest, overriding only what the test cares about
+  async function createTestQuest(
+    opts: {
+      reward?: number;
+      maxClaimers?: number;
+      timeLimit?: BN | null;
+      label?: string;
+      pooled?: boolean;
+      steps?: { descriptionHash: number[]; shareBps: number }[];
+      rewardMint?: PublicKey;
+      creatorTokenAccount?: PublicKey | null;
+    } = {}
+  ): Promise<{ questPda: PublicKey; escrowPda: PublicKey; questId: number }> {
     const id = questCount;
     const [questPda] = deriveQuestPda(id);
     const [escrowPda] = deriveEscrowPda(questPda);
-    const [claimPda] = deriveClaimPda(questPda, claimer.publicKey);
 
     await program.methods
       .createQuest(
-        new BN(REWARD),
+        new BN(opts.reward ?? REWARD),
         { open: {} },
         null,
-        1,
-        null,
-        descHash("test quest for lifecycle")
+        opts.maxClaimers ?? 1,
+        opts.timeLimit ?? null,
+        PROOF_HOURS,
+        REVIEW_HOURS,
+        descHash(opts.label ?? "test quest"),
+        opts.pooled ?? false,
+        opts.steps ?? [],
+        null
       )
       .accounts({
         config: configPda,
         quest: questPda,
+        questDescription: null,
         escrow: escrowPda,
-        rewardMint: mint,
+        rewardMint: opts.rewardMint ?? mint,
         creator: creator.publicKey,
-        creatorTokenAccount: creatorAta,
+        creatorTokenAccount:
+          opts.creatorTokenAccount === undefined ? creatorAta : opts.creatorTokenAccount,
         tokenProgram: TOKEN_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
       })
@@ -131,15 +268,30 @@ describe("quest", () => {
       .rpc();
 
     questCount++;
+    return { questPda, escrowPda, questId: id };
+  }
+
+  // helper: full lifecycle to get a quest into "Submitted" state
+  async function createAndSubmitQuest(): Promise<{
+    questPda: PublicKey;
+    escrowPda: PublicKey;
+    claimPda: PublicKey;
+    questId: number;
+  }> {
+    const { questPda, escrowPda, questId: id } = await createTestQuest({
+      label: "test quest for lifecycle",
+    });
+    const [claimPda] = deriveClaimPda(questPda, claimer.publicKey);
 
     const stakeAmount = (REWARD * MIN_STAKE_BPS) / 10000;
 
     await program.methods
-      .claimQuest(new BN(stakeAmount))
+      .claimQuest(new BN(stakeAmount), [])
       .accounts({
         quest: questPda,
         claim: claimPda,
         escrow: escrowPda,
+        rewardMint: mint,
         claimer: claimer.publicKey,
         claimerTokenAccount: claimerAta,
         tokenProgram: TOKEN_PROGRAM_ID,
@@ -149,7 +301,7 @@ describe("quest", () => {
       .rpc();
 
     await program.methods
-      .submitProof(proofHash("proof-data-here"))
+      .submitProof(proofHash("proof-data-here"), PROOF_URI, PROOF_SECONDS, FRAME_ROOT)
       .accounts({
         quest: questPda,
         claim: claimPda,
@@ -161,6 +313,51 @@ describe("quest", () => {
     return { questPda, escrowPda, claimPda, questId: id };
   }
 
+  async function rejectAsOracle(
+    questPda: PublicKey,
+    claimPda: PublicKey,
+    safetyFlagged: boolean = false
+  ) {
+    await program.methods
+      .rejectCompletion(safetyFlagged)
+      .accounts({
+        config: configPda,
+        oracleSet: oracleSetPda,
+        quest: questPda,
+        claim: claimPda,
+        oracle: authority.publicKey,
+      })
+      .rpc();
+  }
+
+  async function finalizeRejection(
+    questPda: PublicKey,
+    claimPda: PublicKey,
+    who: Keypair = claimer,
+    whoAta: PublicKey = claimerAta
+  ) {
+    await program.methods
+      .finalizeRejection()
+      .accounts({
+        quest: questPda,
+        claim: claimPda,
+        escrow: deriveEscrowPda(questPda)[0],
+        rewardMint: mint,
+        creator: creator.publicKey,
+        creatorTokenAccount: creatorAta,
+        profile: deriveProfilePda(who.publicKey)[0],
+        claimer: who.publicKey,
+        claimerTokenAccount: whoAta,
+        treasury: treasuryAta,
+        unwrapAccount: deriveUnwrapPda(questPda)[0],
+        cranker: randomUser.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([randomUser])
+      .rpc();
+  }
+
   before(async () => {
     // fund all wallets
     await Promise.all([
@@ -221,6 +418,18 @@ describe("quest", () => {
       [Buffer.from("config")],
       program.programId
     );
+    [jurorSetPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("jurors")],
+      program.programId
+    );
+    [oracleSetPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("oracles")],
+      program.programId
+    );
+    [globalStatsPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("global_stats")],
+      program.programId
+    );
   });
 
   // =========================================================================
@@ -230,10 +439,14 @@ describe("quest", () => {
   describe("initialize", () => {
     it("sets up protocol config", async () => {
       await program.methods
-        .initialize(feeBps, burnBps)
+        .initialize(feeBps, burnBps, disputeSettings, deadlineBounds, rateLimits, forfeitSplit)
         .accounts({
           config: configPda,
-          treasury: treasuryAta,
+          jurorSet: jurorSetPda,
+          oracleSet: oracleSetPda,
+          globalStats: globalStatsPda,
+          treasury: authority.publicKey,
+          oracle: authority.publicKey,
           authority: authority.publicKey,
           systemProgram: SystemProgram.programId,
         })
@@ -243,9 +456,19 @@ describe("quest", () => {
       expect(config.authority.toBase58()).to.equal(
         authority.publicKey.toBase58()
       );
+      expect(config.pendingAuthority).to.be.null;
       expect(config.feeBasisPoints).to.equal(feeBps);
       expect(config.burnBasisPoints).to.equal(burnBps);
-      expect(config.questCount.toNumber()).to.equal(0);
+      const globalStats = await program.account.globalStats.fetch(globalStatsPda);
+      expect(globalStats.questCount.toNumber()).to.equal(0);
+      expect(config.forfeitSplit.treasuryBps).to.equal(forfeitSplit.treasuryBps);
+      expect(config.forfeitSplit.burnBps).to.equal(forfeitSplit.burnBps);
+
+      const oracleSet = await program.account.oracleSet.fetch(oracleSetPda);
+      expect(oracleSet.oracles.map((o) => o.toBase58())).to.deep.equal([
+        authority.publicKey.toBase58(),
+      ]);
+      expect(oracleSet.threshold).to.equal(1);
     });
 
     it("rejects fee > 100%", async () => {
@@ -253,10 +476,14 @@ describe("quest", () => {
       // but the point stands for the validation check
       try {
         await program.methods
-          .initialize(10001, 0)
+          .initialize(10001, 0, disputeSettings, deadlineBounds, rateLimits, forfeitSplit)
           .accounts({
             config: configPda,
-            treasury: treasuryAta,
+            jurorSet: jurorSetPda,
+            oracleSet: oracleSetPda,
+            globalStats: globalStatsPda,
+            treasury: authority.publicKey,
+            oracle: authority.publicKey,
             authority: authority.publicKey,
             systemProgram: SystemProgram.programId,
           })
@@ -269,6 +496,227 @@ describe("quest", () => {
     });
   });
 
+  // =========================================================================
+  // Config Updates (timelocked)
+  // =========================================================================
+
+  describe("config updates", () => {
+    const noChange = {
+      treasury: null,
+      feeBasisPoints: null,
+      burnBasisPoints: null,
+      dispute: null,
+      deadlineBounds: null,
+      rateLimits: null,
+      forfeitSplit: null,
+    };
+
+    it("queues a fee change behind the timelock", async () => {
+      await program.methods
+        .updateConfig({ ...noChange, feeBasisPoints: 300 })
+        .accounts({
+          config: configPda,
+          authority: authority.publicKey,
+        })
+        .rpc();
+
+      const config = await program.account.questConfig.fetch(configPda);
+      // current fee is untouched until the update is applied
+      expect(config.feeBasisPoints).to.equal(feeBps);
+      expect(config.pendingUpdate.update.feeBasisPoints).to.equal(300);
+      expect(config.pendingUpdate.effectiveAt.toNumber()).to.be.greaterThan(
+        Math.floor(Date.now() / 1000)
+      );
+    });
+
+    it("cannot apply before the timelock passes", async () => {
+      try {
+        await program.methods
+          .applyConfigUpdate()
+          .accounts({
+            config: configPda,
+            cranker: randomUser.publicKey,
+          })
+          .signers([randomUser])
+          .rpc();
+        expect.fail("should have thrown");
+      } catch (err) {
+        const anchorErr = err as AnchorError;
+        expect(anchorErr.error.errorCode.code).to.equal("TimelockNotElapsed");
+      }
+    });
+
+    it("authority can cancel a queued update", async () => {
+      await program.methods
+        .cancelConfigUpdate()
+        .accounts({
+          config: configPda,
+          authority: authority.publicKey,
+        })
+        .rpc();
+
+      const config = await program.account.questConfig.fetch(configPda);
+      expect(config.pendingUpdate).to.be.null;
+    });
+
+    it("non-authority cannot queue an update", async () => {
+      try {
+        await program.methods
+          .updateConfig({ ...noChange, treasury: randomUser.publicKey })
+          .accounts({
+            config: configPda,
+            authority: randomUser.publicKey,
+          })
+          .signers([randomUser])
+          .rpc();
+        expect.fail("should have thrown");
+      } catch (err) {
+        const anchorErr = err as AnchorError;
+        expect(anchorErr.error.errorCode.code).to.equal("NotAuthority");
+      }
+    });
+
+    it("rejects a queued fee > 100%", async () => {
+      try {
+        await program.methods
+          .updateConfig({ ...noChange, feeBasisPoints: 10001 })
+          .accounts({
+            config: configPda,
+            authority: authority.publicKey,
+          })
+          .rpc();
+        expect.fail("should have thrown");
+      } catch (err) {
+        const anchorErr = err as AnchorError;
+        expect(anchorErr.error.errorCode.code).to.equal("InvalidFeeConfig");
+      }
+    });
+
+    it("rejects a forfeit split that doesn't sum to 100%", async () => {
+      try {
+        await program.methods
+          .updateConfig({
+            ...noChange,
+            forfeitSplit: { creatorBps: 5000, treasuryBps: 3000, burnBps: 1000 },
+          })
+          .accounts({
+            config: configPda,
+            authority: authority.publicKey,
+          })
+          .rpc();
+        expect.fail("should have thrown");
+      } catch (err) {
+        const anchorErr = err as AnchorError;
+        expect(anchorErr.error.errorCode.code).to.equal("InvalidForfeitSplit");
+      }
+    });
+
+    // NOTE: applying after the timelock needs clock warp, same as expire_claim.
+    it.skip("applies the update after the timelock (requires clock warp)", async () => {});
+  });
+
+  // =========================================================================
+  // Authority Handover
+  // =========================================================================
+
+  describe("authority handover", () => {
+    it("proposed authority takes over only after accepting", async () => {
+      await program.methods
+        .proposeAuthority(randomUser.publicKey)
+        .accounts({
+          config: configPda,
+          authority: authority.publicKey,
+        })
+        .rpc();
+
+      let config = await program.account.questConfig.fetch(configPda);
+      expect(config.authority.toBase58()).to.equal(
+        authority.publicKey.toBase58()
+      );
+      expect(config.pendingAuthority.toBase58()).to.equal(
+        randomUser.publicKey.toBase58()
+      );
+
+      await program.methods
+        .acceptAuthority()
+        .accounts({
+          config: configPda,
+          newAuthority: randomUser.publicKey,
+        })
+        .signers([randomUser])
+        .rpc();
+
+      config = await program.account.questConfig.fetch(configPda);
+      expect(config.authority.toBase58()).to.equal(
+        randomUser.publicKey.toBase58()
+      );
+      expect(config.pendingAuthority).to.be.null;
+      // oracle set is untouched by the handover
+      const oracleSet = await program.account.oracleSet.fetch(oracleSetPda);
+      expect(oracleSet.oracles[0].toBase58()).to.equal(
+        authority.publicKey.toBase58()
+      );
+    });
+
+    it("rejects accept from a key that was not proposed", async () => {
+      await program.methods
+        .proposeAuthority(authority.publicKey)
+        .accounts({
+          config: configPda,
+          authority: randomUser.publicKey,
+        })
+        .signers([randomUser])
+        .rpc();
+
+      try {
+        await program.methods
+          .acceptAuthority()
+          .accounts({
+            config: configPda,
+            newAuthority: creator.publicKey,
+          })
+          .signers([creator])
+          .rpc();
+        expect.fail("should have thrown");
+      } catch (err) {
+        const anchorErr = err as AnchorError;
+        expect(anchorErr.error.errorCode.code).to.equal("NotPendingAuthority");
+      }
+    });
+
+    it("hands the admin role back", async () => {
+      await program.methods
+        .acceptAuthority()
+        .accounts({
+          config: configPda,
+          newAuthority: authority.publicKey,
+        })
+        .rpc();
+
+      const config = await program.account.questConfig.fetch(configPda);
+      expect(config.authority.toBase58()).to.equal(
+        authority.publicKey.toBase58()
+      );
+    });
+
+    it("non-authority cannot propose", async () => {
+      try {
+        await program.methods
+          .proposeAuthority(creator.publicKey)
+          .accounts({
+            config: configPda,
+            authority: creator.publicKey,
+          })
+          .signers([creator])
+          .rpc();
+        expect.fail("should have thrown");
+      } catch (err) {
+        const anchorErr = err as AnchorError;
+        expect(anchorErr.error.errorCode.code).to.equal("NotAuthority");
+      }
+    });
+  });
+
   // =========================================================================
   // Create Quest
   // =========================================================================
@@ -286,13 +734,19 @@ describe("quest", () => {
           new BN(REWARD),
           { open: {} },
           null,
-          5,
+          1,
           null,
-          descHash("open quest: review this PR")
+          PROOF_HOURS,
+          REVIEW_HOURS,
+          descHash("open quest: review this PR"),
+          false,
+          [],
+          null
         )
         .accounts({
           config: configPda,
           quest: questPda,
+          questDescription: null,
           escrow: escrowPda,
           rewardMint: mint,
           creator: creator.publicKey,
@@ -310,9 +764,11 @@ describe("quest", () => {
       expect(quest.rewardAmount.toNumber()).to.equal(REWARD);
       expect(quest.questType).to.deep.equal({ open: {} });
       expect(quest.status).to.deep.equal({ active: {} });
-      expect(quest.maxClaimers).to.equal(5);
+      expect(quest.maxClaimers).to.equal(1);
       expect(quest.currentClaimers).to.equal(0);
       expect(quest.target).to.be.null;
+      expect(quest.proofDeadlineHours).to.equal(PROOF_HOURS);
+      expect(quest.reviewDeadlineHours).to.equal(REVIEW_HOURS);
 
       const balAfter = await getTokenBalance(creatorAta);
       expect(balBefore - balAfter).to.equal(REWARD);
@@ -321,24 +777,34 @@ describe("quest", () => {
       expect(escrowBal).to.equal(REWARD);
     });
 
-    it("creates a direct quest with a target", async () => {
+    it("stores the description text on-chain when it matches the hash", async () => {
       const id = questCount;
       const [questPda] = deriveQuestPda(id);
-      const [escrowPda] = deriveEscrowPda(questPda);
+      const [descriptionPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("description"), questPda.toBuffer()],
+        program.programId
+      );
+      const text = "Do 20 pushups in front of the Eiffel Tower 🗼";
 
       await program.methods
         .createQuest(
           new BN(REWARD),
-          { direct: {} },
-          claimer.publicKey,
+          { open: {} },
+          null,
           1,
           null,
-          descHash("direct quest for claimer")
+          PROOF_HOURS,
+          REVIEW_HOURS,
+          [...createHash("sha256").update(text, "utf8").digest()],
+          false,
+          [],
+          text
         )
         .accounts({
           config: configPda,
           quest: questPda,
-          escrow: escrowPda,
+          questDescription: descriptionPda,
+          escrow: deriveEscrowPda(questPda)[0],
           rewardMint: mint,
           creator: creator.publicKey,
           creatorTokenAccount: creatorAta,
@@ -347,13 +813,198 @@ describe("quest", () => {
         })
         .signers([creator])
         .rpc();
-
       questCount++;
 
-      const quest = await program.account.quest.fetch(questPda);
-      expect(quest.questType).to.deep.equal({ direct: {} });
-      expect(quest.target.toBase58()).to.equal(claimer.publicKey.toBase58());
-    });
+      const description = await program.account.questDescription.fetch(descriptionPda);
+      expect(description.quest.toBase58()).to.equal(questPda.toBase58());
+      expect(description.creator.toBase58()).to.equal(creator.publicKey.toBase58());
+      expect(description.text).to.equal(text);
+
+      // cancelling hands the description rent back with the quest's
+      const descriptionRent = (await connection.getAccountInfo(descriptionPda)).lamports;
+      const creatorLamportsBefore = await connection.getBalance(creator.publicKey);
+      await program.methods
+        .cancelQuest()
+        .accounts({
+          quest: questPda,
+          escrow: deriveEscrowPda(questPda)[0],
+          rewardMint: mint,
+          creator: creator.publicKey,
+          creatorTokenAccount: creatorAta,
+          unwrapAccount: deriveUnwrapPda(questPda)[0],
+          questDescription: descriptionPda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([creator])
+        .rpc();
+
+      expect(await isClosed(descriptionPda)).to.be.true;
+      expect(await isClosed(questPda)).to.be.true;
+      expect(
+        (await connection.getBalance(creator.publicKey)) - creatorLamportsBefore
+      ).to.be.greaterThan(descriptionRent);
+    });
+
+    it("a leftover description can be closed once its quest is gone", async () => {
+      const [questPda] = deriveQuestPda(questCount);
+      const [descriptionPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("description"), questPda.toBuffer()],
+        program.programId
+      );
+      const text = "Sing the anthem at the stadium";
+
+      await program.methods
+        .createQuest(
+          new BN(REWARD),
+          { open: {} },
+          null,
+          1,
+          null,
+          PROOF_HOURS,
+          REVIEW_HOURS,
+          [...createHash("sha256").update(text, "utf8").digest()],
+          false,
+          [],
+          text
+        )
+        .accounts({
+          config: configPda,
+          quest: questPda,
+          questDescription: descriptionPda,
+          escrow: deriveEscrowPda(questPda)[0],
+          rewardMint: mint,
+          creator: creator.publicKey,
+          creatorTokenAccount: creatorAta,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([creator])
+        .rpc();
+      questCount++;
+
+      const closeDescription = () =>
+        program.methods
+          .closeDescription()
+          .accounts({
+            quest: questPda,
+            questDescription: descriptionPda,
+            creator: creator.publicKey,
+            cranker: randomUser.publicKey,
+          })
+          .signers([randomUser])
+          .rpc();
+
+      try {
+        await closeDescription();
+        expect.fail("should have thrown");
+      } catch (err) {
+        const anchorErr = err as AnchorError;
+        expect(anchorErr.error.errorCode.code).to.equal("QuestNotSettled");
+      }
+
+      // settled without the description account, so it is left behind
+      await program.methods
+        .cancelQuest()
+        .accounts({
+          quest: questPda,
+          escrow: deriveEscrowPda(questPda)[0],
+          rewardMint: mint,
+          creator: creator.publicKey,
+          creatorTokenAccount: creatorAta,
+          unwrapAccount: deriveUnwrapPda(questPda)[0],
+          questDescription: null,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([creator])
+        .rpc();
+      expect(await isClosed(descriptionPda)).to.be.false;
+
+      await closeDescription();
+      expect(await isClosed(descriptionPda)).to.be.true;
+    });
+
+    it("rejects description text that doesn't match the hash", async () => {
+      const [questPda] = deriveQuestPda(questCount);
+
+      try {
+        await program.methods
+          .createQuest(
+            new BN(REWARD),
+            { open: {} },
+            null,
+            1,
+            null,
+            PROOF_HOURS,
+            REVIEW_HOURS,
+            descHash("the real dare"),
+            false,
+            [],
+            "a different dare"
+          )
+          .accounts({
+            config: configPda,
+            quest: questPda,
+            questDescription: PublicKey.findProgramAddressSync(
+              [Buffer.from("description"), questPda.toBuffer()],
+              program.programId
+            )[0],
+            escrow: deriveEscrowPda(questPda)[0],
+            rewardMint: mint,
+            creator: creator.publicKey,
+            creatorTokenAccount: creatorAta,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([creator])
+          .rpc();
+        expect.fail("should have thrown");
+      } catch (err) {
+        const anchorErr = err as AnchorError;
+        expect(anchorErr.error.errorCode.code).to.equal("DescriptionHashMismatch");
+      }
+    });
+
+    it("creates a direct quest with a target", async () => {
+      const id = questCount;
+      const [questPda] = deriveQuestPda(id);
+      const [escrowPda] = deriveEscrowPda(questPda);
+
+      await program.methods
+        .createQuest(
+          new BN(REWARD),
+          { direct: {} },
+          claimer.publicKey,
+          1,
+          null,
+          PROOF_HOURS,
+          REVIEW_HOURS,
+          descHash("direct quest for claimer"),
+          false,
+          [],
+          null
+        )
+        .accounts({
+          config: configPda,
+          quest: questPda,
+          questDescription: null,
+          escrow: escrowPda,
+          rewardMint: mint,
+          creator: creator.publicKey,
+          creatorTokenAccount: creatorAta,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([creator])
+        .rpc();
+
+      questCount++;
+
+      const quest = await program.account.quest.fetch(questPda);
+      expect(quest.questType).to.deep.equal({ direct: {} });
+      expect(quest.target.toBase58()).to.equal(claimer.publicKey.toBase58());
+    });
 
     it("rejects reward below minimum", async () => {
       const id = questCount;
@@ -368,11 +1019,17 @@ describe("quest", () => {
             null,
             1,
             null,
-            descHash("cheap quest")
+            PROOF_HOURS,
+            REVIEW_HOURS,
+            descHash("cheap quest"),
+            false,
+            [],
+            null
           )
           .accounts({
             config: configPda,
             quest: questPda,
+            questDescription: null,
             escrow: escrowPda,
             rewardMint: mint,
             creator: creator.publicKey,
@@ -402,11 +1059,17 @@ describe("quest", () => {
             creator.publicKey, // targeting self
             1,
             null,
-            descHash("self-target")
+            PROOF_HOURS,
+            REVIEW_HOURS,
+            descHash("self-target"),
+            false,
+            [],
+            null
           )
           .accounts({
             config: configPda,
             quest: questPda,
+            questDescription: null,
             escrow: escrowPda,
             rewardMint: mint,
             creator: creator.publicKey,
@@ -436,11 +1099,17 @@ describe("quest", () => {
             null,
             1,
             null,
-            descHash("no target")
+            PROOF_HOURS,
+            REVIEW_HOURS,
+            descHash("no target"),
+            false,
+            [],
+            null
           )
           .accounts({
             config: configPda,
             quest: questPda,
+            questDescription: null,
             escrow: escrowPda,
             rewardMint: mint,
             creator: creator.publicKey,
@@ -458,6 +1127,51 @@ describe("quest", () => {
         );
       }
     });
+
+    it("rejects deadlines outside the protocol bounds", async () => {
+      const id = questCount;
+      const [questPda] = deriveQuestPda(id);
+      const [escrowPda] = deriveEscrowPda(questPda);
+
+      for (const [proofHours, reviewHours] of [
+        [0, REVIEW_HOURS],
+        [PROOF_HOURS, deadlineBounds.maxReviewHours + 1],
+      ]) {
+        try {
+          await program.methods
+            .createQuest(
+              new BN(REWARD),
+              { open: {} },
+              null,
+              1,
+              null,
+              proofHours,
+              reviewHours,
+              descHash("bad deadlines"),
+              false,
+              [],
+              null
+            )
+            .accounts({
+              config: configPda,
+              quest: questPda,
+              questDescription: null,
+              escrow: escrowPda,
+              rewardMint: mint,
+              creator: creator.publicKey,
+              creatorTokenAccount: creatorAta,
+              tokenProgram: TOKEN_PROGRAM_ID,
+              systemProgram: SystemProgram.programId,
+            })
+            .signers([creator])
+            .rpc();
+          expect.fail("should have thrown");
+        } catch (err) {
+          const anchorErr = err as AnchorError;
+          expect(anchorErr.error.errorCode.code).to.equal("InvalidDeadline");
+        }
+      }
+    });
   });
 
   // =========================================================================
@@ -480,11 +1194,17 @@ describe("quest", () => {
           null,
           1,
           null,
-          descHash("claimable quest")
+          PROOF_HOURS,
+          REVIEW_HOURS,
+          descHash("claimable quest"),
+          false,
+          [],
+          null
         )
         .accounts({
           config: configPda,
           quest: questPda,
+          questDescription: null,
           escrow: escrowPda,
           rewardMint: mint,
           creator: creator.publicKey,
@@ -504,11 +1224,12 @@ describe("quest", () => {
       const claimerBefore = await getTokenBalance(claimerAta);
 
       await program.methods
-        .claimQuest(new BN(stakeAmount))
+        .claimQuest(new BN(stakeAmount), [])
         .accounts({
           quest: questPda,
           claim: claimPda,
           escrow: escrowPda,
+          rewardMint: mint,
           claimer: claimer.publicKey,
           claimerTokenAccount: claimerAta,
           tokenProgram: TOKEN_PROGRAM_ID,
@@ -545,11 +1266,17 @@ describe("quest", () => {
           null,
           1,
           null,
-          descHash("self-claim test")
+          PROOF_HOURS,
+          REVIEW_HOURS,
+          descHash("self-claim test"),
+          false,
+          [],
+          null
         )
         .accounts({
           config: configPda,
           quest: qPda,
+          questDescription: null,
           escrow: ePda,
           rewardMint: mint,
           creator: creator.publicKey,
@@ -565,11 +1292,12 @@ describe("quest", () => {
 
       try {
         await program.methods
-          .claimQuest(new BN((REWARD * MIN_STAKE_BPS) / 10000))
+          .claimQuest(new BN((REWARD * MIN_STAKE_BPS) / 10000), [])
           .accounts({
             quest: qPda,
             claim: claimPda,
             escrow: ePda,
+            rewardMint: mint,
             claimer: creator.publicKey,
             claimerTokenAccount: creatorAta,
             tokenProgram: TOKEN_PROGRAM_ID,
@@ -593,11 +1321,12 @@ describe("quest", () => {
 
       try {
         await program.methods
-          .claimQuest(new BN(1)) // 1 lamport, way too low
+          .claimQuest(new BN(1), []) // 1 lamport, way too low
           .accounts({
             quest: qPda,
             claim: claimPda,
             escrow: ePda,
+            rewardMint: mint,
             claimer: claimer.publicKey,
             claimerTokenAccount: claimerAta,
             tokenProgram: TOKEN_PROGRAM_ID,
@@ -634,11 +1363,17 @@ describe("quest", () => {
           null,
           1,
           null,
-          descHash("proof quest")
+          PROOF_HOURS,
+          REVIEW_HOURS,
+          descHash("proof quest"),
+          false,
+          [],
+          null
         )
         .accounts({
           config: configPda,
           quest: questPda,
+          questDescription: null,
           escrow: escrowPda,
           rewardMint: mint,
           creator: creator.publicKey,
@@ -651,11 +1386,12 @@ describe("quest", () => {
       questCount++;
 
       await program.methods
-        .claimQuest(new BN((REWARD * MIN_STAKE_BPS) / 10000))
+        .claimQuest(new BN((REWARD * MIN_STAKE_BPS) / 10000), [])
         .accounts({
           quest: questPda,
           claim: claimPda,
           escrow: deriveEscrowPda(questPda)[0],
+          rewardMint: mint,
           claimer: claimer.publicKey,
           claimerTokenAccount: claimerAta,
           tokenProgram: TOKEN_PROGRAM_ID,
@@ -665,11 +1401,29 @@ describe("quest", () => {
         .rpc();
     });
 
+    it("rejects an empty proof URI", async () => {
+      try {
+        await program.methods
+          .submitProof(proofHash("no uri"), "", PROOF_SECONDS, FRAME_ROOT)
+          .accounts({
+            quest: questPda,
+            claim: claimPda,
+            claimer: claimer.publicKey,
+          })
+          .signers([claimer])
+          .rpc();
+        expect.fail("should have thrown");
+      } catch (err) {
+        const anchorErr = err as AnchorError;
+        expect(anchorErr.error.errorCode.code).to.equal("InvalidProofUri");
+      }
+    });
+
     it("submits proof and sets review deadline", async () => {
       const hash = proofHash("ipfs://QmSomeHash12345");
 
       await program.methods
-        .submitProof(hash)
+        .submitProof(hash, PROOF_URI, PROOF_SECONDS, FRAME_ROOT)
         .accounts({
           quest: questPda,
           claim: claimPda,
@@ -683,6 +1437,13 @@ describe("quest", () => {
       expect(claim.proofHash).to.not.be.null;
       expect(claim.submittedAt).to.not.be.null;
       expect(claim.reviewDeadline).to.not.be.null;
+
+      const proof = await program.account.proof.fetch(deriveProofPda(claimPda)[0]);
+      expect(proof.claim.toBase58()).to.equal(claimPda.toBase58());
+      expect(proof.proofHash).to.deep.equal(hash);
+      expect(proof.uri).to.equal(PROOF_URI);
+      expect(proof.durationSeconds).to.equal(PROOF_SECONDS);
+      expect(proof.frameRoot).to.deep.equal(FRAME_ROOT);
     });
 
     it("rejects proof from non-claimer", async () => {
@@ -699,11 +1460,17 @@ describe("quest", () => {
           null,
           1,
           null,
-          descHash("non-claimer proof test")
+          PROOF_HOURS,
+          REVIEW_HOURS,
+          descHash("non-claimer proof test"),
+          false,
+          [],
+          null
         )
         .accounts({
           config: configPda,
           quest: qPda,
+          questDescription: null,
           escrow: ePda,
           rewardMint: mint,
           creator: creator.publicKey,
@@ -716,11 +1483,12 @@ describe("quest", () => {
       questCount++;
 
       await program.methods
-        .claimQuest(new BN((REWARD * MIN_STAKE_BPS) / 10000))
+        .claimQuest(new BN((REWARD * MIN_STAKE_BPS) / 10000), [])
         .accounts({
           quest: qPda,
           claim: cPda,
           escrow: ePda,
+          rewardMint: mint,
           claimer: claimer.publicKey,
           claimerTokenAccount: claimerAta,
           tokenProgram: TOKEN_PROGRAM_ID,
@@ -733,7 +1501,7 @@ describe("quest", () => {
       try {
         const [wrongClaimPda] = deriveClaimPda(qPda, randomUser.publicKey);
         await program.methods
-          .submitProof(proofHash("fake"))
+          .submitProof(proofHash("fake"), PROOF_URI, PROOF_SECONDS, FRAME_ROOT)
           .accounts({
             quest: qPda,
             claim: wrongClaimPda,
@@ -759,25 +1527,35 @@ describe("quest", () => {
 
       const claimerBefore = await getTokenBalance(claimerAta);
       const treasuryBefore = await getTokenBalance(treasuryAta);
+      const supplyBefore = Number((await getMint(connection, mint)).supply);
 
       await program.methods
         .approveCompletion()
         .accounts({
           config: configPda,
+          oracleSet: oracleSetPda,
           quest: questPda,
           claim: claimPda,
           escrow: escrowPda,
+          rewardMint: mint,
+          creator: creator.publicKey,
+          creatorTokenAccount: creatorAta,
+          claimer: claimer.publicKey,
           claimerTokenAccount: claimerAta,
           treasury: treasuryAta,
-          authority: authority.publicKey,
+          unwrapAccount: deriveUnwrapPda(questPda)[0],
+          oracle: authority.publicKey,
           tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
         })
         .rpc();
 
       const claimerAfter = await getTokenBalance(claimerAta);
       const treasuryAfter = await getTokenBalance(treasuryAta);
+      const supplyAfter = Number((await getMint(connection, mint)).supply);
 
       const feeAmount = Math.floor((REWARD * feeBps) / 10000);
+      const burnAmount = Math.floor((feeAmount * burnBps) / 10000);
       const rewardAfterFee = REWARD - feeAmount;
       const stakeAmount = (REWARD * MIN_STAKE_BPS) / 10000;
 
@@ -785,14 +1563,15 @@ describe("quest", () => {
       expect(claimerAfter - claimerBefore).to.equal(
         rewardAfterFee + stakeAmount
       );
-      // treasury gets fee
-      expect(treasuryAfter - treasuryBefore).to.equal(feeAmount);
-
-      const quest = await program.account.quest.fetch(questPda);
-      expect(quest.status).to.deep.equal({ completed: {} });
-
-      const claim = await program.account.claim.fetch(claimPda);
-      expect(claim.status).to.deep.equal({ approved: {} });
+      // treasury gets the non-burned share of the fee
+      expect(treasuryAfter - treasuryBefore).to.equal(feeAmount - burnAmount);
+      // burn share is removed from supply
+      expect(supplyBefore - supplyAfter).to.equal(burnAmount);
+
+      // settled quest is closed and its rent returned
+      expect(await isClosed(escrowPda)).to.be.true;
+      expect(await isClosed(questPda)).to.be.true;
+      expect(await isClosed(claimPda)).to.be.true;
     });
 
     it("rejects approval from non-oracle", async () => {
@@ -803,13 +1582,20 @@ describe("quest", () => {
           .approveCompletion()
           .accounts({
             config: configPda,
+            oracleSet: oracleSetPda,
             quest: questPda,
             claim: claimPda,
             escrow: escrowPda,
+            rewardMint: mint,
+            creator: creator.publicKey,
+            creatorTokenAccount: creatorAta,
+            claimer: claimer.publicKey,
             claimerTokenAccount: claimerAta,
             treasury: treasuryAta,
-            authority: creator.publicKey, // not the oracle
+            unwrapAccount: deriveUnwrapPda(questPda)[0],
+            oracle: creator.publicKey, // not the oracle
             tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
           })
           .signers([creator])
           .rpc();
@@ -819,6 +1605,43 @@ describe("quest", () => {
         expect(anchorErr.error.errorCode.code).to.equal("NotOracle");
       }
     });
+
+    it("oracle cannot redirect the payout or the fee", async () => {
+      const { questPda, escrowPda, claimPda } = await createAndSubmitQuest();
+
+      for (const [redirect, code] of [
+        [{ claimerTokenAccount: creatorAta }, "ConstraintRaw"],
+        [{ treasury: creatorAta }, "InvalidTreasury"],
+      ] as const) {
+        try {
+          await program.methods
+            .approveCompletion()
+            .accounts({
+              config: configPda,
+              oracleSet: oracleSetPda,
+              quest: questPda,
+              claim: claimPda,
+              escrow: escrowPda,
+              rewardMint: mint,
+              creator: creator.publicKey,
+              creatorTokenAccount: creatorAta,
+              claimer: claimer.publicKey,
+              claimerTokenAccount: claimerAta,
+              treasury: treasuryAta,
+              unwrapAccount: deriveUnwrapPda(questPda)[0],
+              oracle: authority.publicKey,
+              tokenProgram: TOKEN_PROGRAM_ID,
+              systemProgram: SystemProgram.programId,
+              ...redirect,
+            })
+            .rpc();
+          expect.fail("should have thrown");
+        } catch (err) {
+          const anchorErr = err as AnchorError;
+          expect(anchorErr.error.errorCode.code).to.equal(code);
+        }
+      }
+    });
   });
 
   // =========================================================================
@@ -826,56 +1649,50 @@ describe("quest", () => {
   // =========================================================================
 
   describe("reject completion", () => {
-    it("normal rejection: creator gets reward + stake", async () => {
+    it("normal rejection: creator gets reward + stake once the dispute window closes", async () => {
       const { questPda, escrowPda, claimPda } = await createAndSubmitQuest();
 
       const stakeAmount = (REWARD * MIN_STAKE_BPS) / 10000;
       const creatorBefore = await getTokenBalance(creatorAta);
 
-      await program.methods
-        .rejectCompletion(false)
-        .accounts({
-          config: configPda,
-          quest: questPda,
-          claim: claimPda,
-          escrow: escrowPda,
-          creatorTokenAccount: creatorAta,
-          claimerTokenAccount: claimerAta,
-          authority: authority.publicKey,
-          tokenProgram: TOKEN_PROGRAM_ID,
-        })
-        .rpc();
+      await rejectAsOracle(questPda, claimPda);
 
-      const creatorAfter = await getTokenBalance(creatorAta);
-      expect(creatorAfter - creatorBefore).to.equal(REWARD + stakeAmount);
+      // nothing moves while the claimer can still dispute
+      const claim = await program.account.claim.fetch(claimPda);
+      expect(claim.status).to.deep.equal({ rejectionPending: {} });
+      expect(claim.disputeDeadline).to.not.be.null;
+      expect(await getTokenBalance(escrowPda)).to.equal(REWARD + stakeAmount);
 
-      const quest = await program.account.quest.fetch(questPda);
-      expect(quest.status).to.deep.equal({ failed: {} });
+      try {
+        await finalizeRejection(questPda, claimPda);
+        expect.fail("should have thrown");
+      } catch (err) {
+        const anchorErr = err as AnchorError;
+        expect(anchorErr.error.errorCode.code).to.equal("DeadlineNotReached");
+      }
 
-      const claim = await program.account.claim.fetch(claimPda);
-      expect(claim.status).to.deep.equal({ rejected: {} });
+      await sleep((DISPUTE_WINDOW + 2) * 1000);
+      await finalizeRejection(questPda, claimPda);
+
+      const creatorAfter = await getTokenBalance(creatorAta);
+      expect(creatorAfter - creatorBefore).to.equal(REWARD + creatorForfeitShare(stakeAmount));
+
+      // failed quest is closed and its rent returned
+      expect(await isClosed(escrowPda)).to.be.true;
+      expect(await isClosed(questPda)).to.be.true;
+      expect(await isClosed(claimPda)).to.be.true;
     });
 
     it("safety-flagged rejection: stake returned to claimer", async () => {
-      const { questPda, escrowPda, claimPda } = await createAndSubmitQuest();
+      const { questPda, claimPda } = await createAndSubmitQuest();
 
       const stakeAmount = (REWARD * MIN_STAKE_BPS) / 10000;
       const creatorBefore = await getTokenBalance(creatorAta);
       const claimerBefore = await getTokenBalance(claimerAta);
 
-      await program.methods
-        .rejectCompletion(true) // safety_flagged = true
-        .accounts({
-          config: configPda,
-          quest: questPda,
-          claim: claimPda,
-          escrow: escrowPda,
-          creatorTokenAccount: creatorAta,
-          claimerTokenAccount: claimerAta,
-          authority: authority.publicKey,
-          tokenProgram: TOKEN_PROGRAM_ID,
-        })
-        .rpc();
+      await rejectAsOracle(questPda, claimPda, true); // safety_flagged = true
+      await sleep((DISPUTE_WINDOW + 2) * 1000);
+      await finalizeRejection(questPda, claimPda);
 
       const creatorAfter = await getTokenBalance(creatorAta);
       const claimerAfter = await getTokenBalance(claimerAta);
@@ -884,6 +1701,13 @@ describe("quest", () => {
       expect(creatorAfter - creatorBefore).to.equal(REWARD);
       // claimer gets stake back (not punished)
       expect(claimerAfter - claimerBefore).to.equal(stakeAmount);
+
+      // but the flagged proof counts as a strike
+      const profile = await program.account.userProfile.fetch(
+        deriveProfilePda(claimer.publicKey)[0]
+      );
+      expect(profile.strikes).to.equal(1);
+      expect(profile.banned).to.be.false;
     });
   });
 
@@ -902,13 +1726,19 @@ describe("quest", () => {
           new BN(REWARD),
           { open: {} },
           null,
-          3,
+          1,
           null,
-          descHash("cancellable")
+          PROOF_HOURS,
+          REVIEW_HOURS,
+          descHash("cancellable"),
+          false,
+          [],
+          null
         )
         .accounts({
           config: configPda,
           quest: questPda,
+          questDescription: null,
           escrow: escrowPda,
           rewardMint: mint,
           creator: creator.publicKey,
@@ -927,9 +1757,13 @@ describe("quest", () => {
         .accounts({
           quest: questPda,
           escrow: escrowPda,
+          rewardMint: mint,
           creator: creator.publicKey,
           creatorTokenAccount: creatorAta,
+          unwrapAccount: deriveUnwrapPda(questPda)[0],
+          questDescription: null,
           tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
         })
         .signers([creator])
         .rpc();
@@ -937,8 +1771,8 @@ describe("quest", () => {
       const creatorAfter = await getTokenBalance(creatorAta);
       expect(creatorAfter - creatorBefore).to.equal(REWARD);
 
-      const quest = await program.account.quest.fetch(questPda);
-      expect(quest.status).to.deep.equal({ cancelled: {} });
+      expect(await isClosed(escrowPda)).to.be.true;
+      expect(await isClosed(questPda)).to.be.true;
     });
 
     it("non-creator cannot cancel", async () => {
@@ -953,11 +1787,17 @@ describe("quest", () => {
           null,
           1,
           null,
-          descHash("not yours")
+          PROOF_HOURS,
+          REVIEW_HOURS,
+          descHash("not yours"),
+          false,
+          [],
+          null
         )
         .accounts({
           config: configPda,
           quest: questPda,
+          questDescription: null,
           escrow: escrowPda,
           rewardMint: mint,
           creator: creator.publicKey,
@@ -975,9 +1815,13 @@ describe("quest", () => {
           .accounts({
             quest: questPda,
             escrow: escrowPda,
+            rewardMint: mint,
             creator: claimer.publicKey,
             creatorTokenAccount: claimerAta,
+            unwrapAccount: deriveUnwrapPda(questPda)[0],
+            questDescription: null,
             tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
           })
           .signers([claimer])
           .rpc();
@@ -999,13 +1843,19 @@ describe("quest", () => {
           new BN(REWARD),
           { open: {} },
           null,
-          5,
+          1,
           null,
-          descHash("has claimers")
+          PROOF_HOURS,
+          REVIEW_HOURS,
+          descHash("has claimers"),
+          false,
+          [],
+          null
         )
         .accounts({
           config: configPda,
           quest: questPda,
+          questDescription: null,
           escrow: escrowPda,
           rewardMint: mint,
           creator: creator.publicKey,
@@ -1019,11 +1869,12 @@ describe("quest", () => {
 
       // claim it
       await program.methods
-        .claimQuest(new BN((REWARD * MIN_STAKE_BPS) / 10000))
+        .claimQuest(new BN((REWARD * MIN_STAKE_BPS) / 10000), [])
         .accounts({
           quest: questPda,
           claim: claimPda,
           escrow: escrowPda,
+          rewardMint: mint,
           claimer: claimer.publicKey,
           claimerTokenAccount: claimerAta,
           tokenProgram: TOKEN_PROGRAM_ID,
@@ -1038,9 +1889,13 @@ describe("quest", () => {
           .accounts({
             quest: questPda,
             escrow: escrowPda,
+            rewardMint: mint,
             creator: creator.publicKey,
             creatorTokenAccount: creatorAta,
+            unwrapAccount: deriveUnwrapPda(questPda)[0],
+            questDescription: null,
             tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
           })
           .signers([creator])
           .rpc();
@@ -1071,11 +1926,17 @@ describe("quest", () => {
           null,
           1,
           null,
-          descHash("abandon test")
+          PROOF_HOURS,
+          REVIEW_HOURS,
+          descHash("abandon test"),
+          false,
+          [],
+          null
         )
         .accounts({
           config: configPda,
           quest: questPda,
+          questDescription: null,
           escrow: escrowPda,
           rewardMint: mint,
           creator: creator.publicKey,
@@ -1088,11 +1949,12 @@ describe("quest", () => {
       questCount++;
 
       await program.methods
-        .claimQuest(new BN(stakeAmount))
+        .claimQuest(new BN(stakeAmount), [])
         .accounts({
           quest: questPda,
           claim: claimPda,
           escrow: escrowPda,
+          rewardMint: mint,
           claimer: claimer.publicKey,
           claimerTokenAccount: claimerAta,
           tokenProgram: TOKEN_PROGRAM_ID,
@@ -1106,6 +1968,7 @@ describe("quest", () => {
       expect(quest.status).to.deep.equal({ claimed: {} });
 
       const creatorBefore = await getTokenBalance(creatorAta);
+      const treasuryBefore = await getTokenBalance(treasuryAta);
 
       await program.methods
         .abandonClaim()
@@ -1113,15 +1976,24 @@ describe("quest", () => {
           quest: questPda,
           claim: claimPda,
           escrow: escrowPda,
+          rewardMint: mint,
+          creator: creator.publicKey,
           creatorTokenAccount: creatorAta,
+          treasury: treasuryAta,
+          unwrapAccount: deriveUnwrapPda(questPda)[0],
           claimer: claimer.publicKey,
           tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
         })
         .signers([claimer])
         .rpc();
 
       const creatorAfter = await getTokenBalance(creatorAta);
-      expect(creatorAfter - creatorBefore).to.equal(stakeAmount);
+      expect(creatorAfter - creatorBefore).to.equal(creatorForfeitShare(stakeAmount));
+      // 30% to treasury, the 10% burn share leaves circulation
+      expect((await getTokenBalance(treasuryAta)) - treasuryBefore).to.equal(
+        Math.floor((stakeAmount * forfeitSplit.treasuryBps) / 10000)
+      );
 
       const claim = await program.account.claim.fetch(claimPda);
       expect(claim.status).to.deep.equal({ abandoned: {} });
@@ -1151,11 +2023,17 @@ describe("quest", () => {
           null,
           1,
           null,
-          descHash("expire test")
+          PROOF_HOURS,
+          REVIEW_HOURS,
+          descHash("expire test"),
+          false,
+          [],
+          null
         )
         .accounts({
           config: configPda,
           quest: questPda,
+          questDescription: null,
           escrow: escrowPda,
           rewardMint: mint,
           creator: creator.publicKey,
@@ -1168,11 +2046,12 @@ describe("quest", () => {
       questCount++;
 
       await program.methods
-        .claimQuest(new BN((REWARD * MIN_STAKE_BPS) / 10000))
+        .claimQuest(new BN((REWARD * MIN_STAKE_BPS) / 10000), [])
         .accounts({
           quest: questPda,
           claim: claimPda,
           escrow: escrowPda,
+          rewardMint: mint,
           claimer: claimer.publicKey,
           claimerTokenAccount: claimerAta,
           tokenProgram: TOKEN_PROGRAM_ID,
@@ -1188,10 +2067,16 @@ describe("quest", () => {
           .accounts({
             quest: questPda,
             claim: claimPda,
+            profile: deriveProfilePda(claimer.publicKey)[0],
             escrow: escrowPda,
+            rewardMint: mint,
+            creator: creator.publicKey,
             creatorTokenAccount: creatorAta,
+            treasury: treasuryAta,
+            unwrapAccount: deriveUnwrapPda(questPda)[0],
             cranker: randomUser.publicKey,
             tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
           })
           .signers([randomUser])
           .rpc();
@@ -1227,10 +2112,16 @@ describe("quest", () => {
             quest: questPda,
             claim: claimPda,
             escrow: escrowPda,
+            rewardMint: mint,
+            creator: creator.publicKey,
+            creatorTokenAccount: creatorAta,
+            claimer: claimer.publicKey,
             claimerTokenAccount: claimerAta,
             treasury: treasuryAta,
+            unwrapAccount: deriveUnwrapPda(questPda)[0],
             cranker: randomUser.publicKey,
             tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
           })
           .signers([randomUser])
           .rpc();
@@ -1254,11 +2145,17 @@ describe("quest", () => {
           null,
           1,
           null,
-          descHash("auto-approve guard")
+          PROOF_HOURS,
+          REVIEW_HOURS,
+          descHash("auto-approve guard"),
+          false,
+          [],
+          null
         )
         .accounts({
           config: configPda,
           quest: questPda,
+          questDescription: null,
           escrow: escrowPda,
           rewardMint: mint,
           creator: creator.publicKey,
@@ -1271,11 +2168,12 @@ describe("quest", () => {
       questCount++;
 
       await program.methods
-        .claimQuest(new BN((REWARD * MIN_STAKE_BPS) / 10000))
+        .claimQuest(new BN((REWARD * MIN_STAKE_BPS) / 10000), [])
         .accounts({
           quest: questPda,
           claim: claimPda,
           escrow: escrowPda,
+          rewardMint: mint,
           claimer: claimer.publicKey,
           claimerTokenAccount: claimerAta,
           tokenProgram: TOKEN_PROGRAM_ID,
@@ -1292,10 +2190,16 @@ describe("quest", () => {
             quest: questPda,
             claim: claimPda,
             escrow: escrowPda,
+            rewardMint: mint,
+            creator: creator.publicKey,
+            creatorTokenAccount: creatorAta,
+            claimer: claimer.publicKey,
             claimerTokenAccount: claimerAta,
             treasury: treasuryAta,
+            unwrapAccount: deriveUnwrapPda(questPda)[0],
             cranker: randomUser.publicKey,
             tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
           })
           .signers([randomUser])
           .rpc();
@@ -1306,12 +2210,2994 @@ describe("quest", () => {
       }
     });
 
+    it("cranker cannot mix quests or redirect the payout", async () => {
+      const first = await createAndSubmitQuest();
+      const second = await createAndSubmitQuest();
+
+      for (const [overrides, code] of [
+        [{ claim: second.claimPda }, "ConstraintRaw"],
+        [{ claimerTokenAccount: creatorAta }, "ConstraintRaw"],
+        [{ treasury: creatorAta }, "InvalidTreasury"],
+      ] as const) {
+        try {
+          await program.methods
+            .autoApprove()
+            .accounts({
+              config: configPda,
+              quest: first.questPda,
+              claim: first.claimPda,
+              escrow: first.escrowPda,
+              rewardMint: mint,
+              creator: creator.publicKey,
+              creatorTokenAccount: creatorAta,
+              claimer: claimer.publicKey,
+              claimerTokenAccount: claimerAta,
+              treasury: treasuryAta,
+              unwrapAccount: deriveUnwrapPda(first.questPda)[0],
+              cranker: randomUser.publicKey,
+              tokenProgram: TOKEN_PROGRAM_ID,
+              systemProgram: SystemProgram.programId,
+              ...overrides,
+            })
+            .signers([randomUser])
+            .rpc();
+          expect.fail("should have thrown");
+        } catch (err) {
+          const anchorErr = err as AnchorError;
+          expect(anchorErr.error.errorCode.code).to.equal(code);
+        }
+      }
+    });
+
     // NOTE: Full auto-approve test requires clock warp past review_deadline.
     it.skip("auto-approves after review deadline (requires clock warp)", async () => {
       // same limitation as expire_claim — needs bankrun or clock manipulation
     });
   });
 
+  // =========================================================================
+  // Pooled Quests (per-claimer reward pool)
+  // =========================================================================
+
+  describe("pooled quests", () => {
+    const claimer2 = Keypair.generate();
+    let claimer2Ata: PublicKey;
+    const stakeAmount = (REWARD * MIN_STAKE_BPS) / 10000;
+
+    before(async () => {
+      await airdrop(claimer2.publicKey);
+      claimer2Ata = await createAccount(
+        connection,
+        (authority as any).payer,
+        mint,
+        claimer2.publicKey
+      );
+      await mintTo(
+        connection,
+        (authority as any).payer,
+        mint,
+        claimer2Ata,
+        authority.publicKey,
+        10_000 * ONE_TOKEN
+      );
+    });
+
+    async function createPooledQuest(
+      maxClaimers: number,
+      text: string
+    ): Promise<{ questPda: PublicKey; escrowPda: PublicKey }> {
+      return createTestQuest({ maxClaimers, pooled: true, label: text });
+    }
+
+    async function claimAndSubmit(
+      questPda: PublicKey,
+      escrowPda: PublicKey,
+      who: Keypair,
+      whoAta: PublicKey
+    ): Promise<PublicKey> {
+      const [claimPda] = deriveClaimPda(questPda, who.publicKey);
+
+      await program.methods
+        .claimQuest(new BN(stakeAmount), [])
+        .accounts({
+          quest: questPda,
+          claim: claimPda,
+          escrow: escrowPda,
+          rewardMint: mint,
+          claimer: who.publicKey,
+          claimerTokenAccount: whoAta,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([who])
+        .rpc();
+
+      await program.methods
+        .submitProof(proofHash("pooled proof"), PROOF_URI, PROOF_SECONDS, FRAME_ROOT)
+        .accounts({
+          quest: questPda,
+          claim: claimPda,
+          claimer: who.publicKey,
+        })
+        .signers([who])
+        .rpc();
+
+      return claimPda;
+    }
+
+    async function approve(
+      questPda: PublicKey,
+      escrowPda: PublicKey,
+      claimPda: PublicKey,
+      who: Keypair,
+      whoAta: PublicKey
+    ) {
+      await program.methods
+        .approveCompletion()
+        .accounts({
+          config: configPda,
+          oracleSet: oracleSetPda,
+          quest: questPda,
+          claim: claimPda,
+          escrow: escrowPda,
+          rewardMint: mint,
+          creator: creator.publicKey,
+          creatorTokenAccount: creatorAta,
+          claimer: who.publicKey,
+          claimerTokenAccount: whoAta,
+          treasury: treasuryAta,
+          unwrapAccount: deriveUnwrapPda(questPda)[0],
+          oracle: authority.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+    }
+
+    it("escrows the reward for every slot", async () => {
+      const creatorBefore = await getTokenBalance(creatorAta);
+      const { questPda, escrowPda } = await createPooledQuest(3, "pool of 3");
+
+      expect(await getTokenBalance(escrowPda)).to.equal(3 * REWARD);
+      expect(creatorBefore - (await getTokenBalance(creatorAta))).to.equal(
+        3 * REWARD
+      );
+
+      const quest = await program.account.quest.fetch(questPda);
+      expect(quest.pooled).to.be.true;
+      expect(quest.rewardAmount.toNumber()).to.equal(REWARD);
+      expect(quest.completedClaimers).to.equal(0);
+    });
+
+    it("pays each approved claimer and completes after the last slot", async () => {
+      const { questPda, escrowPda } = await createPooledQuest(2, "pool of 2");
+      const claimA = await claimAndSubmit(questPda, escrowPda, claimer, claimerAta);
+      const claimB = await claimAndSubmit(questPda, escrowPda, claimer2, claimer2Ata);
+
+      const feeAmount = Math.floor((REWARD * feeBps) / 10000);
+      const claimerBefore = await getTokenBalance(claimerAta);
+
+      await approve(questPda, escrowPda, claimA, claimer, claimerAta);
+
+      expect((await getTokenBalance(claimerAta)) - claimerBefore).to.equal(
+        REWARD - feeAmount + stakeAmount
+      );
+      let quest = await program.account.quest.fetch(questPda);
+      expect(quest.status).to.deep.equal({ claimed: {} });
+      expect(quest.completedClaimers).to.equal(1);
+      expect(quest.currentClaimers).to.equal(1);
+
+      await approve(questPda, escrowPda, claimB, claimer2, claimer2Ata);
+
+      // last slot paid: the quest completes and is closed along with its escrow
+      expect(await isClosed(questPda)).to.be.true;
+      expect(await isClosed(escrowPda)).to.be.true;
+    });
+
+    it("rejection keeps the reward in the pool and reopens the slot", async () => {
+      const { questPda, escrowPda } = await createPooledQuest(1, "pool reject");
+      const claimPda = await claimAndSubmit(questPda, escrowPda, claimer, claimerAta);
+
+      const creatorBefore = await getTokenBalance(creatorAta);
+
+      await rejectAsOracle(questPda, claimPda);
+      await sleep((DISPUTE_WINDOW + 2) * 1000);
+      await finalizeRejection(questPda, claimPda);
+
+      // creator only collects its share of the forfeited stake
+      expect((await getTokenBalance(creatorAta)) - creatorBefore).to.equal(
+        creatorForfeitShare(stakeAmount)
+      );
+      expect(await getTokenBalance(escrowPda)).to.equal(REWARD);
+
+      const quest = await program.account.quest.fetch(questPda);
+      expect(quest.status).to.deep.equal({ active: {} });
+      expect(quest.currentClaimers).to.equal(0);
+    });
+
+    it("cancel refunds only the unused slots", async () => {
+      const { questPda, escrowPda } = await createPooledQuest(3, "pool cancel");
+      const claimPda = await claimAndSubmit(questPda, escrowPda, claimer, claimerAta);
+      await approve(questPda, escrowPda, claimPda, claimer, claimerAta);
+
+      const creatorBefore = await getTokenBalance(creatorAta);
+
+      await program.methods
+        .cancelQuest()
+        .accounts({
+          quest: questPda,
+          escrow: escrowPda,
+          rewardMint: mint,
+          creator: creator.publicKey,
+          creatorTokenAccount: creatorAta,
+          unwrapAccount: deriveUnwrapPda(questPda)[0],
+          questDescription: null,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([creator])
+        .rpc();
+
+      expect((await getTokenBalance(creatorAta)) - creatorBefore).to.equal(
+        2 * REWARD
+      );
+      expect(await isClosed(escrowPda)).to.be.true;
+    });
+
+    it("rejects a pooled direct quest", async () => {
+      const [questPda] = deriveQuestPda(questCount);
+      const [escrowPda] = deriveEscrowPda(questPda);
+
+      try {
+        await program.methods
+          .createQuest(
+            new BN(REWARD),
+            { direct: {} },
+            claimer.publicKey,
+            2,
+            null,
+            PROOF_HOURS,
+            REVIEW_HOURS,
+            descHash("pooled direct"),
+            true,
+            [],
+            null
+          )
+          .accounts({
+            config: configPda,
+            quest: questPda,
+            questDescription: null,
+            escrow: escrowPda,
+            rewardMint: mint,
+            creator: creator.publicKey,
+            creatorTokenAccount: creatorAta,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([creator])
+          .rpc();
+        expect.fail("should have thrown");
+      } catch (err) {
+        const anchorErr = err as AnchorError;
+        expect(anchorErr.error.errorCode.code).to.equal("PooledQuestMustBeOpen");
+      }
+    });
+  });
+
+  // =========================================================================
+  // Native SOL (wrap on deposit, unwrap on payout)
+  // =========================================================================
+
+  describe("native SOL", () => {
+    const SOL_REWARD = LAMPORTS_PER_SOL / 10;
+    const solStake = (SOL_REWARD * MIN_STAKE_BPS) / 10000;
+    let wsolTreasury: PublicKey;
+
+    before(async () => {
+      wsolTreasury = await createAccount(
+        connection,
+        (authority as any).payer,
+        NATIVE_MINT,
+        authority.publicKey,
+        Keypair.generate()
+      );
+    });
+
+    async function createSolQuest(
+      text: string
+    ): Promise<{ questPda: PublicKey; escrowPda: PublicKey }> {
+      return createTestQuest({
+        reward: SOL_REWARD,
+        label: text,
+        rewardMint: NATIVE_MINT,
+        creatorTokenAccount: null,
+      });
+    }
+
+    it("wraps the creator's lamports into escrow", async () => {
+      const { questPda, escrowPda } = await createSolQuest("sol quest");
+
+      expect(await getTokenBalance(escrowPda)).to.equal(SOL_REWARD);
+
+      const quest = await program.account.quest.fetch(questPda);
+      expect(quest.rewardMint.toBase58()).to.equal(NATIVE_MINT.toBase58());
+    });
+
+    it("unwraps the payout to the claimer's wallet on approval", async () => {
+      const { questPda, escrowPda } = await createSolQuest("sol payout");
+      const [claimPda] = deriveClaimPda(questPda, claimer.publicKey);
+
+      await program.methods
+        .claimQuest(new BN(solStake), [])
+        .accounts({
+          quest: questPda,
+          claim: claimPda,
+          escrow: escrowPda,
+          rewardMint: NATIVE_MINT,
+          claimer: claimer.publicKey,
+          claimerTokenAccount: null,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([claimer])
+        .rpc();
+
+      expect(await getTokenBalance(escrowPda)).to.equal(SOL_REWARD + solStake);
+
+      await program.methods
+        .submitProof(proofHash("sol proof"), PROOF_URI, PROOF_SECONDS, FRAME_ROOT)
+        .accounts({
+          quest: questPda,
+          claim: claimPda,
+          claimer: claimer.publicKey,
+        })
+        .signers([claimer])
+        .rpc();
+
+      const claimerBefore = await connection.getBalance(claimer.publicKey);
+      const treasuryBefore = await getTokenBalance(wsolTreasury);
+      const claimRent = (await connection.getAccountInfo(claimPda)).lamports;
+
+      await program.methods
+        .approveCompletion()
+        .accounts({
+          config: configPda,
+          oracleSet: oracleSetPda,
+          quest: questPda,
+          claim: claimPda,
+          escrow: escrowPda,
+          rewardMint: NATIVE_MINT,
+          creator: creator.publicKey,
+          creatorTokenAccount: null,
+          claimer: claimer.publicKey,
+          claimerTokenAccount: null,
+          treasury: wsolTreasury,
+          unwrapAccount: deriveUnwrapPda(questPda)[0],
+          oracle: authority.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      const feeAmount = Math.floor((SOL_REWARD * feeBps) / 10000);
+
+      // claimer also gets the claim rent back once the quest settles
+      expect(
+        (await connection.getBalance(claimer.publicKey)) - claimerBefore
+      ).to.equal(SOL_REWARD - feeAmount + solStake + claimRent);
+      // native SOL can't be burned, so the whole fee goes to treasury
+      expect((await getTokenBalance(wsolTreasury)) - treasuryBefore).to.equal(
+        feeAmount
+      );
+      expect(await isClosed(escrowPda)).to.be.true;
+
+      // scratch unwrap account is closed again
+      const unwrapInfo = await connection.getAccountInfo(
+        deriveUnwrapPda(questPda)[0]
+      );
+      expect(unwrapInfo).to.be.null;
+    });
+
+    it("cancel returns lamports to the creator", async () => {
+      const { questPda, escrowPda } = await createSolQuest("sol cancel");
+      const creatorBefore = await connection.getBalance(creator.publicKey);
+
+      await program.methods
+        .cancelQuest()
+        .accounts({
+          quest: questPda,
+          escrow: escrowPda,
+          rewardMint: NATIVE_MINT,
+          creator: creator.publicKey,
+          creatorTokenAccount: null,
+          unwrapAccount: deriveUnwrapPda(questPda)[0],
+          questDescription: null,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([creator])
+        .rpc();
+
+      // refund plus quest and escrow rent, less the tx fee
+      const delta = (await connection.getBalance(creator.publicKey)) - creatorBefore;
+      expect(delta).to.be.greaterThan(SOL_REWARD);
+      expect(await isClosed(escrowPda)).to.be.true;
+    });
+  });
+
+  // =========================================================================
+  // Token-2022 (transfer-fee mints)
+  // =========================================================================
+
+  describe("token-2022", () => {
+    const transferFeeBps = 100; // 1%
+    const feeMint = Keypair.generate();
+    let creatorFeeAta: PublicKey;
+    let claimerFeeAta: PublicKey;
+
+    before(async () => {
+      const payer = (authority as any).payer as Keypair;
+      const mintLen = getMintLen([ExtensionType.TransferFeeConfig]);
+      const lamports = await connection.getMinimumBalanceForRentExemption(
+        mintLen
+      );
+
+      await sendAndConfirmTransaction(
+        connection,
+        new Transaction().add(
+          SystemProgram.createAccount({
+            fromPubkey: payer.publicKey,
+            newAccountPubkey: feeMint.publicKey,
+            space: mintLen,
+            lamports,
+            programId: TOKEN_2022_PROGRAM_ID,
+          }),
+          createInitializeTransferFeeConfigInstruction(
+            feeMint.publicKey,
+            payer.publicKey,
+            payer.publicKey,
+            transferFeeBps,
+            BigInt(1_000 * ONE_TOKEN),
+            TOKEN_2022_PROGRAM_ID
+          ),
+          createInitializeMintInstruction(
+            feeMint.publicKey,
+            DECIMALS,
+            payer.publicKey,
+            null,
+            TOKEN_2022_PROGRAM_ID
+          )
+        ),
+        [payer, feeMint]
+      );
+
+      creatorFeeAta = await createAccount(
+        connection,
+        payer,
+        feeMint.publicKey,
+        creator.publicKey,
+        undefined,
+        undefined,
+        TOKEN_2022_PROGRAM_ID
+      );
+      claimerFeeAta = await createAccount(
+        connection,
+        payer,
+        feeMint.publicKey,
+        claimer.publicKey,
+        undefined,
+        undefined,
+        TOKEN_2022_PROGRAM_ID
+      );
+      for (const ata of [creatorFeeAta, claimerFeeAta]) {
+        await mintTo(
+          connection,
+          payer,
+          feeMint.publicKey,
+          ata,
+          payer,
+          10_000 * ONE_TOKEN,
+          [],
+          undefined,
+          TOKEN_2022_PROGRAM_ID
+        );
+      }
+    });
+
+    it("records the reward and stake actually received by escrow", async () => {
+      const [questPda] = deriveQuestPda(questCount);
+      const [escrowPda] = deriveEscrowPda(questPda);
+      const [claimPda] = deriveClaimPda(questPda, claimer.publicKey);
+
+      await program.methods
+        .createQuest(
+          new BN(REWARD),
+          { open: {} },
+          null,
+          1,
+          null,
+          PROOF_HOURS,
+          REVIEW_HOURS,
+          descHash("token-2022 quest"),
+          false,
+          [],
+          null
+        )
+        .accounts({
+          config: configPda,
+          quest: questPda,
+          questDescription: null,
+          escrow: escrowPda,
+          rewardMint: feeMint.publicKey,
+          creator: creator.publicKey,
+          creatorTokenAccount: creatorFeeAta,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([creator])
+        .rpc();
+      questCount++;
+
+      const netReward = REWARD - (REWARD * transferFeeBps) / 10000;
+      const quest = await program.account.quest.fetch(questPda);
+      expect(quest.rewardAmount.toNumber()).to.equal(netReward);
+      expect(
+        await getTokenBalance(escrowPda, TOKEN_2022_PROGRAM_ID)
+      ).to.equal(netReward);
+
+      // stake sized off the net reward, plus headroom for the transfer fee
+      const stakeSent = Math.ceil(
+        ((netReward * MIN_STAKE_BPS) / 10000) * 1.02
+      );
+
+      await program.methods
+        .claimQuest(new BN(stakeSent), [])
+        .accounts({
+          quest: questPda,
+          claim: claimPda,
+          escrow: escrowPda,
+          rewardMint: feeMint.publicKey,
+          claimer: claimer.publicKey,
+          claimerTokenAccount: claimerFeeAta,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([claimer])
+        .rpc();
+
+      const stakeFee = Math.ceil((stakeSent * transferFeeBps) / 10000);
+      const claim = await program.account.claim.fetch(claimPda);
+      expect(claim.stakeAmount.toNumber()).to.equal(stakeSent - stakeFee);
+      expect(
+        await getTokenBalance(escrowPda, TOKEN_2022_PROGRAM_ID)
+      ).to.equal(netReward + stakeSent - stakeFee);
+    });
+  });
+
+  // =========================================================================
+  // Close Accounts (rent reclaim)
+  // =========================================================================
+
+  describe("close accounts", () => {
+    const claimer2 = Keypair.generate();
+    let claimer2Ata: PublicKey;
+    const stakeAmount = (REWARD * MIN_STAKE_BPS) / 10000;
+
+    before(async () => {
+      await airdrop(claimer2.publicKey);
+      claimer2Ata = await createAccount(
+        connection,
+        (authority as any).payer,
+        mint,
+        claimer2.publicKey
+      );
+      await mintTo(
+        connection,
+        (authority as any).payer,
+        mint,
+        claimer2Ata,
+        authority.publicKey,
+        10_000 * ONE_TOKEN
+      );
+    });
+
+    async function closeQuest(questPda: PublicKey) {
+      await program.methods
+        .closeQuest()
+        .accounts({
+          quest: questPda,
+          escrow: deriveEscrowPda(questPda)[0],
+          rewardMint: mint,
+          creator: creator.publicKey,
+          creatorTokenAccount: creatorAta,
+          unwrapAccount: deriveUnwrapPda(questPda)[0],
+          questDescription: null,
+          cranker: randomUser.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([randomUser])
+        .rpc();
+    }
+
+    async function closeClaim(questPda: PublicKey, who: Keypair) {
+      await program.methods
+        .closeClaim()
+        .accounts({
+          quest: questPda,
+          claim: deriveClaimPda(questPda, who.publicKey)[0],
+          claimer: who.publicKey,
+          cranker: randomUser.publicKey,
+        })
+        .signers([randomUser])
+        .rpc();
+    }
+
+    async function closeProof(claimPda: PublicKey, who: Keypair) {
+      await program.methods
+        .closeProof()
+        .accounts({
+          claim: claimPda,
+          proof: deriveProofPda(claimPda)[0],
+          claimer: who.publicKey,
+          cranker: randomUser.publicKey,
+        })
+        .signers([randomUser])
+        .rpc();
+    }
+
+    async function claimWith(
+      questPda: PublicKey,
+      who: Keypair,
+      whoAta: PublicKey
+    ) {
+      await program.methods
+        .claimQuest(new BN(stakeAmount), [])
+        .accounts({
+          quest: questPda,
+          claim: deriveClaimPda(questPda, who.publicKey)[0],
+          escrow: deriveEscrowPda(questPda)[0],
+          rewardMint: mint,
+          claimer: who.publicKey,
+          claimerTokenAccount: whoAta,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([who])
+        .rpc();
+    }
+
+    it("cannot close a quest that is still open", async () => {
+      const { questPda } = await createAndSubmitQuest();
+
+      try {
+        await closeQuest(questPda);
+        expect.fail("should have thrown");
+      } catch (err) {
+        const anchorErr = err as AnchorError;
+        expect(anchorErr.error.errorCode.code).to.equal("QuestNotSettled");
+      }
+    });
+
+    it("cannot close a claim that is still live", async () => {
+      const { questPda, claimPda } = await createAndSubmitQuest();
+
+      try {
+        await closeClaim(questPda, claimer);
+        expect.fail("should have thrown");
+      } catch (err) {
+        const anchorErr = err as AnchorError;
+        expect(anchorErr.error.errorCode.code).to.equal("ClaimNotSettled");
+      }
+
+      try {
+        await closeProof(claimPda, claimer);
+        expect.fail("should have thrown");
+      } catch (err) {
+        const anchorErr = err as AnchorError;
+        expect(anchorErr.error.errorCode.code).to.equal("ClaimNotSettled");
+      }
+    });
+
+    it("closes an abandoned claim once the quest completes", async () => {
+      const { questPda, escrowPda } = await createTestQuest({
+        label: "close after settle",
+      });
+      const [claimPda] = deriveClaimPda(questPda, claimer.publicKey);
+      const [claim2Pda] = deriveClaimPda(questPda, claimer2.publicKey);
+
+      // a non-pooled quest holds one claimer at a time
+      await claimWith(questPda, claimer2, claimer2Ata);
+      try {
+        await claimWith(questPda, claimer, claimerAta);
+        expect.fail("should have thrown");
+      } catch (err) {
+        const anchorErr = err as AnchorError;
+        expect(anchorErr.error.errorCode.code).to.equal("QuestNotActive");
+      }
+
+      await program.methods
+        .abandonClaim()
+        .accounts({
+          quest: questPda,
+          claim: claim2Pda,
+          escrow: escrowPda,
+          rewardMint: mint,
+          creator: creator.publicKey,
+          creatorTokenAccount: creatorAta,
+          treasury: treasuryAta,
+          unwrapAccount: deriveUnwrapPda(questPda)[0],
+          claimer: claimer2.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([claimer2])
+        .rpc();
+
+      await claimWith(questPda, claimer, claimerAta);
+
+      await program.methods
+        .submitProof(proofHash("close proof"), PROOF_URI, PROOF_SECONDS, FRAME_ROOT)
+        .accounts({
+          quest: questPda,
+          claim: claimPda,
+          claimer: claimer.publicKey,
+        })
+        .signers([claimer])
+        .rpc();
+
+      const creatorLamportsBefore = await connection.getBalance(creator.publicKey);
+      const questRent = (await connection.getAccountInfo(questPda)).lamports;
+      const escrowRent = (await connection.getAccountInfo(escrowPda)).lamports;
+
+      await program.methods
+        .approveCompletion()
+        .accounts({
+          config: configPda,
+          oracleSet: oracleSetPda,
+          quest: questPda,
+          claim: claimPda,
+          escrow: escrowPda,
+          rewardMint: mint,
+          creator: creator.publicKey,
+          creatorTokenAccount: creatorAta,
+          claimer: claimer.publicKey,
+          claimerTokenAccount: claimerAta,
+          treasury: treasuryAta,
+          unwrapAccount: deriveUnwrapPda(questPda)[0],
+          oracle: authority.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      // the only live claim settled, so the quest closed with it
+      expect(await isClosed(claimPda)).to.be.true;
+      expect(await isClosed(questPda)).to.be.true;
+      expect(await isClosed(escrowPda)).to.be.true;
+      expect(
+        (await connection.getBalance(creator.publicKey)) - creatorLamportsBefore
+      ).to.equal(questRent + escrowRent);
+      await closeProof(claimPda, claimer);
+      expect(await isClosed(deriveProofPda(claimPda)[0])).to.be.true;
+
+      // quest is gone, so the abandoned claim can be closed too
+      const claimerLamportsBefore = await connection.getBalance(claimer2.publicKey);
+      const claimRent = (await connection.getAccountInfo(claim2Pda)).lamports;
+
+      await closeClaim(questPda, claimer2);
+
+      expect(await isClosed(claim2Pda)).to.be.true;
+      expect(
+        (await connection.getBalance(claimer2.publicKey)) - claimerLamportsBefore
+      ).to.equal(claimRent);
+    });
+
+    it("abandoned claim stays open while the quest is active", async () => {
+      const { questPda, escrowPda } = await createTestQuest({
+        label: "abandon then close",
+      });
+      const [claimPda] = deriveClaimPda(questPda, claimer2.publicKey);
+
+      await claimWith(questPda, claimer2, claimer2Ata);
+
+      await program.methods
+        .abandonClaim()
+        .accounts({
+          quest: questPda,
+          claim: claimPda,
+          escrow: escrowPda,
+          rewardMint: mint,
+          creator: creator.publicKey,
+          creatorTokenAccount: creatorAta,
+          treasury: treasuryAta,
+          unwrapAccount: deriveUnwrapPda(questPda)[0],
+          claimer: claimer2.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([claimer2])
+        .rpc();
+
+      // closing now would let the claimer re-claim the slot
+      try {
+        await closeClaim(questPda, claimer2);
+        expect.fail("should have thrown");
+      } catch (err) {
+        const anchorErr = err as AnchorError;
+        expect(anchorErr.error.errorCode.code).to.equal("QuestNotSettled");
+      }
+    });
+  });
+
+  // =========================================================================
+  // Creator Review (creator settles directly, no oracle)
+  // =========================================================================
+
+  describe("creator review", () => {
+    const stakeAmount = (REWARD * MIN_STAKE_BPS) / 10000;
+
+    it("creator approves and the claimer is paid like an oracle approval", async () => {
+      const { questPda, escrowPda, claimPda } = await createAndSubmitQuest();
+
+      const claimerBefore = await getTokenBalance(claimerAta);
+      const treasuryBefore = await getTokenBalance(treasuryAta);
+
+      await program.methods
+        .creatorApprove()
+        .accounts({
+          config: configPda,
+          quest: questPda,
+          claim: claimPda,
+          escrow: escrowPda,
+          rewardMint: mint,
+          creatorTokenAccount: creatorAta,
+          claimer: claimer.publicKey,
+          claimerTokenAccount: claimerAta,
+          treasury: treasuryAta,
+          unwrapAccount: deriveUnwrapPda(questPda)[0],
+          creator: creator.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([creator])
+        .rpc();
+
+      const feeAmount = Math.floor((REWARD * feeBps) / 10000);
+      const burnAmount = Math.floor((feeAmount * burnBps) / 10000);
+
+      expect((await getTokenBalance(claimerAta)) - claimerBefore).to.equal(
+        REWARD - feeAmount + stakeAmount
+      );
+      expect((await getTokenBalance(treasuryAta)) - treasuryBefore).to.equal(
+        feeAmount - burnAmount
+      );
+      expect(await isClosed(questPda)).to.be.true;
+    });
+
+    it("creator rejection is held for the dispute window like an oracle one", async () => {
+      const { questPda, escrowPda, claimPda } = await createAndSubmitQuest();
+
+      await program.methods
+        .creatorReject()
+        .accounts({
+          config: configPda,
+          quest: questPda,
+          claim: claimPda,
+          creator: creator.publicKey,
+        })
+        .signers([creator])
+        .rpc();
+
+      const claim = await program.account.claim.fetch(claimPda);
+      expect(claim.status).to.deep.equal({ rejectionPending: {} });
+      expect(claim.safetyFlagged).to.be.false;
+      expect(await getTokenBalance(escrowPda)).to.equal(REWARD + stakeAmount);
+    });
+
+    it("only the quest creator can settle", async () => {
+      const { questPda, claimPda } = await createAndSubmitQuest();
+
+      try {
+        await program.methods
+          .creatorReject()
+          .accounts({
+            config: configPda,
+            quest: questPda,
+            claim: claimPda,
+            creator: randomUser.publicKey,
+          })
+          .signers([randomUser])
+          .rpc();
+        expect.fail("should have thrown");
+      } catch (err) {
+        const anchorErr = err as AnchorError;
+        expect(anchorErr.error.errorCode.code).to.equal("NotCreator");
+      }
+    });
+
+    it("fee cannot be redirected away from the configured treasury", async () => {
+      const { questPda, escrowPda, claimPda } = await createAndSubmitQuest();
+
+      try {
+        await program.methods
+          .creatorApprove()
+          .accounts({
+            config: configPda,
+            quest: questPda,
+            claim: claimPda,
+            escrow: escrowPda,
+            rewardMint: mint,
+            creatorTokenAccount: creatorAta,
+            claimer: claimer.publicKey,
+            claimerTokenAccount: claimerAta,
+            treasury: creatorAta,
+            unwrapAccount: deriveUnwrapPda(questPda)[0],
+            creator: creator.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([creator])
+          .rpc();
+        expect.fail("should have thrown");
+      } catch (err) {
+        const anchorErr = err as AnchorError;
+        expect(anchorErr.error.errorCode.code).to.equal("InvalidTreasury");
+      }
+    });
+
+    // NOTE: settling after review_deadline needs clock warp, same as auto_approve.
+    it.skip("creator cannot settle after the review deadline (requires clock warp)", async () => {});
+  });
+
+  // =========================================================================
+  // Disputes
+  // =========================================================================
+
+  describe("disputes", () => {
+    const jurors = [Keypair.generate(), Keypair.generate(), Keypair.generate()];
+    const stakeAmount = (REWARD * MIN_STAKE_BPS) / 10000;
+
+    function deriveDisputePda(claimPda: PublicKey): [PublicKey, number] {
+      return PublicKey.findProgramAddressSync(
+        [Buffer.from("dispute"), claimPda.toBuffer()],
+        program.programId
+      );
+    }
+
+    async function openDispute(questPda: PublicKey, claimPda: PublicKey) {
+      await program.methods
+        .openDispute()
+        .accounts({
+          config: configPda,
+          quest: questPda,
+          claim: claimPda,
+          dispute: deriveDisputePda(claimPda)[0],
+          claimer: claimer.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([claimer])
+        .rpc();
+    }
+
+    async function vote(claimPda: PublicKey, juror: Keypair, forClaimer: boolean) {
+      await program.methods
+        .castVote(forClaimer)
+        .accounts({
+          jurorSet: jurorSetPda,
+          dispute: deriveDisputePda(claimPda)[0],
+          juror: juror.publicKey,
+        })
+        .signers([juror])
+        .rpc();
+    }
+
+    function resolve(questPda: PublicKey, claimPda: PublicKey) {
+      return program.methods.resolveDispute().accounts({
+        config: configPda,
+        quest: questPda,
+        claim: claimPda,
+        dispute: deriveDisputePda(claimPda)[0],
+        profile: deriveProfilePda(claimer.publicKey)[0],
+        escrow: deriveEscrowPda(questPda)[0],
+        rewardMint: mint,
+        creator: creator.publicKey,
+        creatorTokenAccount: creatorAta,
+        claimer: claimer.publicKey,
+        claimerTokenAccount: claimerAta,
+        treasury: treasuryAta,
+        unwrapAccount: deriveUnwrapPda(questPda)[0],
+        cranker: randomUser.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      }).signers([randomUser]);
+    }
+
+    before(async () => {
+      for (const juror of jurors) {
+        await airdrop(juror.publicKey);
+        await program.methods
+          .addJuror(juror.publicKey)
+          .accounts({
+            config: configPda,
+            jurorSet: jurorSetPda,
+            authority: authority.publicKey,
+          })
+          .rpc();
+      }
+    });
+
+    it("non-authority cannot add a juror", async () => {
+      try {
+        await program.methods
+          .addJuror(randomUser.publicKey)
+          .accounts({
+            config: configPda,
+            jurorSet: jurorSetPda,
+            authority: randomUser.publicKey,
+          })
+          .signers([randomUser])
+          .rpc();
+        expect.fail("should have thrown");
+      } catch (err) {
+        const anchorErr = err as AnchorError;
+        expect(anchorErr.error.errorCode.code).to.equal("NotAuthority");
+      }
+    });
+
+    it("claimer wins a dispute and is paid as if approved", async () => {
+      const { questPda, escrowPda, claimPda } = await createAndSubmitQuest();
+      const [disputePda] = deriveDisputePda(claimPda);
+
+      await rejectAsOracle(questPda, claimPda);
+      await openDispute(questPda, claimPda);
+
+      const claim = await program.account.claim.fetch(claimPda);
+      expect(claim.status).to.deep.equal({ disputed: {} });
+      const dispute = await program.account.dispute.fetch(disputePda);
+      expect(dispute.bond.toNumber()).to.equal(DISPUTE_BOND);
+
+      await vote(claimPda, jurors[0], true);
+      await vote(claimPda, jurors[1], true);
+      await vote(claimPda, jurors[2], false);
+
+      try {
+        await vote(claimPda, jurors[0], false);
+        expect.fail("should have thrown");
+      } catch (err) {
+        const anchorErr = err as AnchorError;
+        expect(anchorErr.error.errorCode.code).to.equal("AlreadyVoted");
+      }
+
+      try {
+        await vote(claimPda, randomUser, true);
+        expect.fail("should have thrown");
+      } catch (err) {
+        const anchorErr = err as AnchorError;
+        expect(anchorErr.error.errorCode.code).to.equal("NotJuror");
+      }
+
+      try {
+        await resolve(questPda, claimPda).rpc();
+        expect.fail("should have thrown");
+      } catch (err) {
+        const anchorErr = err as AnchorError;
+        expect(anchorErr.error.errorCode.code).to.equal("DeadlineNotReached");
+      }
+
+      await sleep((VOTING_SECONDS + 2) * 1000);
+
+      const feeAmount = Math.floor((REWARD * feeBps) / 10000);
+      const claimerBefore = await getTokenBalance(claimerAta);
+      const claimerLamportsBefore = await connection.getBalance(claimer.publicKey);
+
+      await resolve(questPda, claimPda).rpc();
+
+      expect((await getTokenBalance(claimerAta)) - claimerBefore).to.equal(
+        REWARD - feeAmount + stakeAmount
+      );
+      // bond comes back with the dispute account's rent
+      expect(
+        (await connection.getBalance(claimer.publicKey)) - claimerLamportsBefore
+      ).to.be.greaterThan(DISPUTE_BOND);
+      expect(await isClosed(disputePda)).to.be.true;
+      expect(await isClosed(escrowPda)).to.be.true;
+    });
+
+    it("upheld rejection forfeits the bond to the majority jurors", async () => {
+      const { questPda, claimPda } = await createAndSubmitQuest();
+      const [disputePda] = deriveDisputePda(claimPda);
+
+      await rejectAsOracle(questPda, claimPda);
+      await openDispute(questPda, claimPda);
+
+      await vote(claimPda, jurors[0], false);
+      await vote(claimPda, jurors[1], true);
+      await vote(claimPda, jurors[2], false);
+
+      await sleep((VOTING_SECONDS + 2) * 1000);
+
+      const creatorBefore = await getTokenBalance(creatorAta);
+      const juror0Before = await connection.getBalance(jurors[0].publicKey);
+      const juror2Before = await connection.getBalance(jurors[2].publicKey);
+
+      // winning jurors in vote order
+      await resolve(questPda, claimPda)
+        .remainingAccounts([
+          { pubkey: jurors[0].publicKey, isWritable: true, isSigner: false },
+          { pubkey: jurors[2].publicKey, isWritable: true, isSigner: false },
+        ])
+        .rpc();
+
+      expect((await getTokenBalance(creatorAta)) - creatorBefore).to.equal(
+        REWARD + creatorForfeitShare(stakeAmount)
+      );
+      expect(
+        (await connection.getBalance(jurors[0].publicKey)) - juror0Before
+      ).to.equal(DISPUTE_BOND / 2);
+      expect(
+        (await connection.getBalance(jurors[2].publicKey)) - juror2Before
+      ).to.equal(DISPUTE_BOND / 2);
+      expect(await isClosed(disputePda)).to.be.true;
+    });
+
+    it("cannot open a dispute once the window has closed", async () => {
+      const { questPda, claimPda } = await createAndSubmitQuest();
+
+      await rejectAsOracle(questPda, claimPda);
+      await sleep((DISPUTE_WINDOW + 2) * 1000);
+
+      try {
+        await openDispute(questPda, claimPda);
+        expect.fail("should have thrown");
+      } catch (err) {
+        const anchorErr = err as AnchorError;
+        expect(anchorErr.error.errorCode.code).to.equal("DisputeWindowClosed");
+      }
+    });
+  });
+
+  // =========================================================================
+  // Oracle Quorum
+  // =========================================================================
+
+  describe("oracle quorum", () => {
+    const secondOracle = Keypair.generate();
+
+    function manageOracles(signer: Keypair = authority.payer) {
+      return {
+        config: configPda,
+        oracleSet: oracleSetPda,
+        authority: signer.publicKey,
+      };
+    }
+
+    async function approveAs(
+      oracle: Keypair,
+      questPda: PublicKey,
+      claimPda: PublicKey
+    ) {
+      await program.methods
+        .approveCompletion()
+        .accounts({
+          config: configPda,
+          oracleSet: oracleSetPda,
+          quest: questPda,
+          claim: claimPda,
+          escrow: deriveEscrowPda(questPda)[0],
+          rewardMint: mint,
+          creator: creator.publicKey,
+          creatorTokenAccount: creatorAta,
+          claimer: claimer.publicKey,
+          claimerTokenAccount: claimerAta,
+          treasury: treasuryAta,
+          unwrapAccount: deriveUnwrapPda(questPda)[0],
+          oracle: oracle.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([oracle])
+        .rpc();
+    }
+
+    before(async () => {
+      await airdrop(secondOracle.publicKey);
+    });
+
+    it("non-authority cannot add an oracle", async () => {
+      try {
+        await program.methods
+          .addOracle(randomUser.publicKey)
+          .accounts(manageOracles(randomUser))
+          .signers([randomUser])
+          .rpc();
+        expect.fail("should have thrown");
+      } catch (err) {
+        const anchorErr = err as AnchorError;
+        expect(anchorErr.error.errorCode.code).to.equal("NotAuthority");
+      }
+    });
+
+    it("threshold cannot exceed the number of oracles", async () => {
+      try {
+        await program.methods
+          .setOracleThreshold(2)
+          .accounts(manageOracles())
+          .rpc();
+        expect.fail("should have thrown");
+      } catch (err) {
+        const anchorErr = err as AnchorError;
+        expect(anchorErr.error.errorCode.code).to.equal("InvalidOracleThreshold");
+      }
+    });
+
+    it("2-of-2 quorum settles only after both oracles approve", async () => {
+      await program.methods
+        .addOracle(secondOracle.publicKey)
+        .accounts(manageOracles())
+        .rpc();
+      await program.methods
+        .setOracleThreshold(2)
+        .accounts(manageOracles())
+        .rpc();
+
+      const { questPda, escrowPda, claimPda } = await createAndSubmitQuest();
+      const stakeAmount = (REWARD * MIN_STAKE_BPS) / 10000;
+
+      await approveAs(authority.payer, questPda, claimPda);
+      // a repeat vote from the same oracle does not count twice
+      await approveAs(authority.payer, questPda, claimPda);
+
+      let claim = await program.account.claim.fetch(claimPda);
+      expect(claim.status).to.deep.equal({ submitted: {} });
+      expect(claim.oracleVotes.length).to.equal(1);
+      expect(await getTokenBalance(escrowPda)).to.equal(REWARD + stakeAmount);
+
+      const claimerBefore = await getTokenBalance(claimerAta);
+      await approveAs(secondOracle, questPda, claimPda);
+
+      const feeAmount = Math.floor((REWARD * feeBps) / 10000);
+      expect((await getTokenBalance(claimerAta)) - claimerBefore).to.equal(
+        REWARD - feeAmount + stakeAmount
+      );
+      expect(await isClosed(claimPda)).to.be.true;
+    });
+
+    it("split verdicts do not settle", async () => {
+      const { questPda, escrowPda, claimPda } = await createAndSubmitQuest();
+      const stakeAmount = (REWARD * MIN_STAKE_BPS) / 10000;
+
+      await approveAs(authority.payer, questPda, claimPda);
+      await program.methods
+        .rejectCompletion(false)
+        .accounts({
+          config: configPda,
+          oracleSet: oracleSetPda,
+          quest: questPda,
+          claim: claimPda,
+          oracle: secondOracle.publicKey,
+        })
+        .signers([secondOracle])
+        .rpc();
+
+      const claim = await program.account.claim.fetch(claimPda);
+      expect(claim.status).to.deep.equal({ submitted: {} });
+      expect(await getTokenBalance(escrowPda)).to.equal(REWARD + stakeAmount);
+
+      // second oracle changes its mind, which completes the quorum
+      await approveAs(secondOracle, questPda, claimPda);
+      expect(await isClosed(claimPda)).to.be.true;
+    });
+
+    it("cannot remove an oracle below the threshold", async () => {
+      try {
+        await program.methods
+          .removeOracle(secondOracle.publicKey)
+          .accounts(manageOracles())
+          .rpc();
+        expect.fail("should have thrown");
+      } catch (err) {
+        const anchorErr = err as AnchorError;
+        expect(anchorErr.error.errorCode.code).to.equal("InvalidOracleThreshold");
+      }
+    });
+
+    after(async () => {
+      // back to a single oracle for the rest of the suite
+      await program.methods
+        .setOracleThreshold(1)
+        .accounts(manageOracles())
+        .rpc();
+      await program.methods
+        .removeOracle(secondOracle.publicKey)
+        .accounts(manageOracles())
+        .rpc();
+    });
+  });
+
+  // =========================================================================
+  // Attested Settlement (relayed oracle signatures)
+  // =========================================================================
+
+  describe("attested settlement", () => {
+    const relayer = Keypair.generate();
+
+    function attest(
+      claimPda: PublicKey,
+      approve: boolean,
+      safetyFlags: number = 0,
+      signer: Keypair = authority.payer,
+      // what createAndSubmitQuest submits
+      proof: number[] = proofHash("proof-data-here"),
+      // a fresh claim has accepted no attestations yet
+      nonce: number = 1
+    ) {
+      const attestation = {
+        claim: claimPda,
+        proofHash: proof,
+        decision: approve ? { approve: {} } : { reject: {} },
+        confidence: 92,
+        safetyFlags,
+        nonce: new BN(nonce),
+      };
+      const ed25519Ix = Ed25519Program.createInstructionWithPrivateKey({
+        privateKey: signer.secretKey,
+        message: encodeAttestation({
+          ...attestation,
+          approve,
+          programId: program.programId,
+        }),
+      });
+      return { attestation, ed25519Ix };
+    }
+
+    function approveAccounts(
+      questPda: PublicKey,
+      claimPda: PublicKey,
+      by: Keypair = relayer
+    ) {
+      return {
+        config: configPda,
+        oracleSet: oracleSetPda,
+        quest: questPda,
+        claim: claimPda,
+        escrow: deriveEscrowPda(questPda)[0],
+        rewardMint: mint,
+        creator: creator.publicKey,
+        creatorTokenAccount: creatorAta,
+        claimer: claimer.publicKey,
+        claimerTokenAccount: claimerAta,
+        treasury: treasuryAta,
+        unwrapAccount: deriveUnwrapPda(questPda)[0],
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+        relayer: by.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      };
+    }
+
+    before(async () => {
+      await airdrop(relayer.publicKey);
+    });
+
+    it("anyone can relay an oracle-signed approval", async () => {
+      const { questPda, claimPda } = await createAndSubmitQuest();
+      const { attestation, ed25519Ix } = attest(claimPda, true);
+
+      const claimerBefore = await getTokenBalance(claimerAta);
+
+      await program.methods
+        .approveWithAttestation(attestation)
+        .accounts(approveAccounts(questPda, claimPda))
+        .preInstructions([ed25519Ix])
+        .signers([relayer])
+        .rpc();
+
+      const feeAmount = Math.floor((REWARD * feeBps) / 10000);
+      const stakeAmount = (REWARD * MIN_STAKE_BPS) / 10000;
+      expect((await getTokenBalance(claimerAta)) - claimerBefore).to.equal(
+        REWARD - feeAmount + stakeAmount
+      );
+      expect(await isClosed(claimPda)).to.be.true;
+    });
+
+    it("relayed rejection with safety flags spares the stake", async () => {
+      const { questPda, claimPda } = await createAndSubmitQuest();
+      const { attestation, ed25519Ix } = attest(claimPda, false, 0b10);
+
+      await program.methods
+        .rejectWithAttestation(attestation)
+        .accounts({
+          config: configPda,
+          oracleSet: oracleSetPda,
+          quest: questPda,
+          claim: claimPda,
+          instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+          relayer: relayer.publicKey,
+        })
+        .preInstructions([ed25519Ix])
+        .signers([relayer])
+        .rpc();
+
+      const claim = await program.account.claim.fetch(claimPda);
+      expect(claim.status).to.deep.equal({ rejectionPending: {} });
+      expect(claim.safetyFlagged).to.be.true;
+      expect(claim.attestationNonce.toString()).to.equal(
+        attestation.nonce.toString()
+      );
+    });
+
+    it("rejects an attestation signed by a non-oracle", async () => {
+      const { questPda, claimPda } = await createAndSubmitQuest();
+      const { attestation, ed25519Ix } = attest(claimPda, true, 0, randomUser);
+
+      try {
+        await program.methods
+          .approveWithAttestation(attestation)
+          .accounts(approveAccounts(questPda, claimPda))
+          .preInstructions([ed25519Ix])
+          .signers([relayer])
+          .rpc();
+        expect.fail("should have thrown");
+      } catch (err) {
+        const anchorErr = err as AnchorError;
+        expect(anchorErr.error.errorCode.code).to.equal("NotOracle");
+      }
+    });
+
+    it("rejects an attestation whose signed message differs", async () => {
+      const { questPda, claimPda } = await createAndSubmitQuest();
+      const { attestation, ed25519Ix } = attest(claimPda, true);
+
+      try {
+        await program.methods
+          .approveWithAttestation({ ...attestation, confidence: 100 })
+          .accounts(approveAccounts(questPda, claimPda))
+          .preInstructions([ed25519Ix])
+          .signers([relayer])
+          .rpc();
+        expect.fail("should have thrown");
+      } catch (err) {
+        const anchorErr = err as AnchorError;
+        expect(anchorErr.error.errorCode.code).to.equal("AttestationMismatch");
+      }
+    });
+
+    it("rejects an attestation signed over a different proof", async () => {
+      const { questPda, claimPda } = await createAndSubmitQuest();
+      const { attestation, ed25519Ix } = attest(
+        claimPda,
+        true,
+        0,
+        authority.payer,
+        proofHash("an earlier proof")
+      );
+
+      try {
+        await program.methods
+          .approveWithAttestation(attestation)
+          .accounts(approveAccounts(questPda, claimPda))
+          .preInstructions([ed25519Ix])
+          .signers([relayer])
+          .rpc();
+        expect.fail("should have thrown");
+      } catch (err) {
+        const anchorErr = err as AnchorError;
+        expect(anchorErr.error.errorCode.code).to.equal("AttestationMismatch");
+      }
+    });
+
+    it("rejects a signature over the bare attestation", async () => {
+      const { questPda, claimPda } = await createAndSubmitQuest();
+      const { attestation } = attest(claimPda, true);
+      // signed without the domain tag and program id
+      const ed25519Ix = Ed25519Program.createInstructionWithPrivateKey({
+        privateKey: authority.payer.secretKey,
+        message: encodeAttestation({
+          ...attestation,
+          approve: true,
+          programId: program.programId,
+        }).subarray(ATTESTATION_DOMAIN.length + 32),
+      });
+
+      try {
+        await program.methods
+          .approveWithAttestation(attestation)
+          .accounts(approveAccounts(questPda, claimPda))
+          .preInstructions([ed25519Ix])
+          .signers([relayer])
+          .rpc();
+        expect.fail("should have thrown");
+      } catch (err) {
+        const anchorErr = err as AnchorError;
+        expect(anchorErr.error.errorCode.code).to.equal("AttestationMismatch");
+      }
+    });
+
+    it("rejects a settlement without the Ed25519 instruction", async () => {
+      const { questPda, claimPda } = await createAndSubmitQuest();
+      const { attestation } = attest(claimPda, true);
+
+      try {
+        await program.methods
+          .approveWithAttestation(attestation)
+          .accounts(approveAccounts(questPda, claimPda))
+          .signers([relayer])
+          .rpc();
+        expect.fail("should have thrown");
+      } catch (err) {
+        const anchorErr = err as AnchorError;
+        expect(anchorErr.error.errorCode.code).to.equal("MissingAttestation");
+      }
+    });
+
+    it("rejects a replayed nonce", async () => {
+      const { questPda, claimPda } = await createAndSubmitQuest();
+
+      // 2-of-2 so the first relay records a vote without settling
+      const secondOracle = Keypair.generate();
+      const manage = {
+        config: configPda,
+        oracleSet: oracleSetPda,
+        authority: authority.publicKey,
+      };
+      await program.methods.addOracle(secondOracle.publicKey).accounts(manage).rpc();
+      await program.methods.setOracleThreshold(2).accounts(manage).rpc();
+
+      try {
+        const { attestation, ed25519Ix } = attest(claimPda, true);
+        await program.methods
+          .approveWithAttestation(attestation)
+          .accounts(approveAccounts(questPda, claimPda))
+          .preInstructions([ed25519Ix])
+          .signers([relayer])
+          .rpc();
+
+        // a different relayer, so it is a new transaction rather than a duplicate
+        try {
+          await program.methods
+            .approveWithAttestation(attestation)
+            .accounts(approveAccounts(questPda, claimPda, claimer))
+            .preInstructions([ed25519Ix])
+            .signers([claimer])
+            .rpc();
+          expect.fail("should have thrown");
+        } catch (err) {
+          const anchorErr = err as AnchorError;
+          expect(anchorErr.error.errorCode.code).to.equal("StaleAttestationNonce");
+        }
+
+        // the counter moves one at a time, so a nonce cannot be skipped ahead
+        const claim = await program.account.claim.fetch(claimPda);
+        expect(claim.attestationNonce.toNumber()).to.equal(1);
+        const skipped = attest(
+          claimPda,
+          true,
+          0,
+          authority.payer,
+          proofHash("proof-data-here"),
+          3
+        );
+        try {
+          await program.methods
+            .approveWithAttestation(skipped.attestation)
+            .accounts(approveAccounts(questPda, claimPda))
+            .preInstructions([skipped.ed25519Ix])
+            .signers([relayer])
+            .rpc();
+          expect.fail("should have thrown");
+        } catch (err) {
+          const anchorErr = err as AnchorError;
+          expect(anchorErr.error.errorCode.code).to.equal("StaleAttestationNonce");
+        }
+      } finally {
+        await program.methods.setOracleThreshold(1).accounts(manage).rpc();
+        await program.methods.removeOracle(secondOracle.publicKey).accounts(manage).rpc();
+      }
+    });
+  });
+
+  // =========================================================================
+  // Expire Quest (time limit passed)
+  // =========================================================================
+
+  describe("expire quest", () => {
+    const TIME_LIMIT_SECONDS = 3;
+
+    async function createTimedQuest(maxClaimers: number, pooled: boolean) {
+      const now = await connection.getBlockTime(await connection.getSlot());
+      return createTestQuest({
+        maxClaimers,
+        pooled,
+        timeLimit: new BN(now + TIME_LIMIT_SECONDS),
+        label: "timed quest",
+      });
+    }
+
+    function expire(questPda: PublicKey) {
+      return program.methods
+        .expireQuest()
+        .accounts({
+          quest: questPda,
+          escrow: deriveEscrowPda(questPda)[0],
+          rewardMint: mint,
+          creator: creator.publicKey,
+          creatorTokenAccount: creatorAta,
+          unwrapAccount: deriveUnwrapPda(questPda)[0],
+          cranker: randomUser.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([randomUser])
+        .rpc();
+    }
+
+    it("refunds an unclaimed quest once the time limit passes", async () => {
+      const { questPda, escrowPda } = await createTimedQuest(1, false);
+
+      try {
+        await expire(questPda);
+        expect.fail("should have thrown");
+      } catch (err) {
+        const anchorErr = err as AnchorError;
+        expect(anchorErr.error.errorCode.code).to.equal("DeadlineNotReached");
+      }
+
+      await sleep((TIME_LIMIT_SECONDS + 2) * 1000);
+
+      const creatorBefore = await getTokenBalance(creatorAta);
+      await expire(questPda);
+
+      expect((await getTokenBalance(creatorAta)) - creatorBefore).to.equal(REWARD);
+      expect(await isClosed(escrowPda)).to.be.true;
+      expect(await isClosed(questPda)).to.be.true;
+    });
+
+    it("expiring the last live claim closes an expired quest", async () => {
+      const { questPda, escrowPda } = await createTimedQuest(1, false);
+      const [claimPda] = deriveClaimPda(questPda, claimer.publicKey);
+      const stakeAmount = (REWARD * MIN_STAKE_BPS) / 10000;
+
+      await program.methods
+        .claimQuest(new BN(stakeAmount), [])
+        .accounts({
+          quest: questPda,
+          claim: claimPda,
+          escrow: escrowPda,
+          rewardMint: mint,
+          claimer: claimer.publicKey,
+          claimerTokenAccount: claimerAta,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([claimer])
+        .rpc();
+
+      await sleep((TIME_LIMIT_SECONDS + 2) * 1000);
+
+      // the claim is still live, so the reward stays in escrow for now
+      await expire(questPda);
+      expect(await getTokenBalance(escrowPda)).to.equal(REWARD + stakeAmount);
+
+      const creatorBefore = await getTokenBalance(creatorAta);
+      const expireClaim = (refundTo: PublicKey) =>
+        program.methods
+          .expireClaim()
+          .accounts({
+            quest: questPda,
+            claim: claimPda,
+            profile: deriveProfilePda(claimer.publicKey)[0],
+            escrow: escrowPda,
+            rewardMint: mint,
+            creator: creator.publicKey,
+            creatorTokenAccount: refundTo,
+            treasury: treasuryAta,
+            unwrapAccount: deriveUnwrapPda(questPda)[0],
+            cranker: randomUser.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([randomUser])
+          .rpc();
+
+      // the cranker cannot redirect the refund
+      try {
+        await expireClaim(claimerAta);
+        expect.fail("should have thrown");
+      } catch (err) {
+        const anchorErr = err as AnchorError;
+        expect(anchorErr.error.errorCode.code).to.equal("ConstraintRaw");
+      }
+
+      await expireClaim(creatorAta);
+
+      expect((await getTokenBalance(creatorAta)) - creatorBefore).to.equal(
+        REWARD + creatorForfeitShare(stakeAmount)
+      );
+      expect(await isClosed(escrowPda)).to.be.true;
+      expect(await isClosed(questPda)).to.be.true;
+    });
+
+    it("pooled: refunds open slots and leaves live claims to settle", async () => {
+      const { questPda, escrowPda } = await createTimedQuest(3, true);
+      const [claimPda] = deriveClaimPda(questPda, claimer.publicKey);
+      const stakeAmount = (REWARD * MIN_STAKE_BPS) / 10000;
+
+      await program.methods
+        .claimQuest(new BN(stakeAmount), [])
+        .accounts({
+          quest: questPda,
+          claim: claimPda,
+          escrow: escrowPda,
+          rewardMint: mint,
+          claimer: claimer.publicKey,
+          claimerTokenAccount: claimerAta,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([claimer])
+        .rpc();
+      // claimed seconds before the time limit, so the proof window is cut short
+      const claim = await program.account.claim.fetch(claimPda);
+      const timed = await program.account.quest.fetch(questPda);
+      expect(claim.proofDeadline.toNumber()).to.equal(timed.timeLimit.toNumber());
+
+      await program.methods
+        .submitProof(proofHash("pooled timed proof"), PROOF_URI, PROOF_SECONDS, FRAME_ROOT)
+        .accounts({ quest: questPda, claim: claimPda, claimer: claimer.publicKey })
+        .signers([claimer])
+        .rpc();
+
+      await sleep((TIME_LIMIT_SECONDS + 2) * 1000);
+
+      const creatorBefore = await getTokenBalance(creatorAta);
+      await expire(questPda);
+
+      // two untaken slots come back, one slot plus the stake stay for the live claim
+      expect((await getTokenBalance(creatorAta)) - creatorBefore).to.equal(
+        2 * REWARD
+      );
+      expect(await getTokenBalance(escrowPda)).to.equal(REWARD + stakeAmount);
+      const quest = await program.account.quest.fetch(questPda);
+      expect(quest.status).to.deep.equal({ expired: {} });
+
+      // the live claim's stake is still in escrow, so the quest cannot close yet
+      try {
+        await program.methods
+          .closeQuest()
+          .accounts({
+            quest: questPda,
+            escrow: escrowPda,
+            rewardMint: mint,
+            creator: creator.publicKey,
+            creatorTokenAccount: creatorAta,
+            unwrapAccount: deriveUnwrapPda(questPda)[0],
+            questDescription: null,
+            cranker: randomUser.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([randomUser])
+          .rpc();
+        expect.fail("should have thrown");
+      } catch (err) {
+        const anchorErr = err as AnchorError;
+        expect(anchorErr.error.errorCode.code).to.equal("LiveClaimsRemaining");
+      }
+
+      await program.methods
+        .approveCompletion()
+        .accounts({
+          config: configPda,
+          oracleSet: oracleSetPda,
+          quest: questPda,
+          claim: claimPda,
+          escrow: escrowPda,
+          rewardMint: mint,
+          creator: creator.publicKey,
+          creatorTokenAccount: creatorAta,
+          claimer: claimer.publicKey,
+          claimerTokenAccount: claimerAta,
+          treasury: treasuryAta,
+          unwrapAccount: deriveUnwrapPda(questPda)[0],
+          oracle: authority.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      // last live claim settled, so the expired quest closes
+      expect(await isClosed(claimPda)).to.be.true;
+      expect(await isClosed(questPda)).to.be.true;
+    });
+
+    it("quest without a time limit cannot be expired", async () => {
+      const { questPda } = await createAndSubmitQuest();
+
+      try {
+        await expire(questPda);
+        expect.fail("should have thrown");
+      } catch (err) {
+        const anchorErr = err as AnchorError;
+        expect(anchorErr.error.errorCode.code).to.equal("NoTimeLimit");
+      }
+    });
+  });
+
+  // =========================================================================
+  // Crowdfunded Rewards
+  // =========================================================================
+
+  describe("crowdfunded rewards", () => {
+    const backer = Keypair.generate();
+    let backerAta: PublicKey;
+    const TOP_UP = 50 * ONE_TOKEN;
+
+    function deriveContributionPda(questPda: PublicKey): [PublicKey, number] {
+      return PublicKey.findProgramAddressSync(
+        [Buffer.from("contribution"), questPda.toBuffer(), backer.publicKey.toBuffer()],
+        program.programId
+      );
+    }
+
+    async function createOpenQuest(): Promise<PublicKey> {
+      return (await createTestQuest({ label: "crowdfunded dare" })).questPda;
+    }
+
+    function addToReward(questPda: PublicKey, amount: number) {
+      return program.methods
+        .addToReward(new BN(amount))
+        .accounts({
+          quest: questPda,
+          contribution: deriveContributionPda(questPda)[0],
+          escrow: deriveEscrowPda(questPda)[0],
+          rewardMint: mint,
+          contributor: backer.publicKey,
+          contributorTokenAccount: backerAta,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([backer])
+        .rpc();
+    }
+
+    function refundContribution(questPda: PublicKey) {
+      return program.methods
+        .refundContribution()
+        .accounts({
+          quest: questPda,
+          contribution: deriveContributionPda(questPda)[0],
+          escrow: deriveEscrowPda(questPda)[0],
+          rewardMint: mint,
+          contributor: backer.publicKey,
+          contributorTokenAccount: backerAta,
+          creator: creator.publicKey,
+          creatorTokenAccount: creatorAta,
+          unwrapAccount: deriveUnwrapPda(questPda)[0],
+          cranker: randomUser.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([randomUser])
+        .rpc();
+    }
+
+    before(async () => {
+      await airdrop(backer.publicKey);
+      backerAta = await createAccount(
+        connection,
+        (authority as any).payer,
+        mint,
+        backer.publicKey
+      );
+      await mintTo(
+        connection,
+        (authority as any).payer,
+        mint,
+        backerAta,
+        authority.publicKey,
+        1_000 * ONE_TOKEN
+      );
+    });
+
+    it("anyone can top up an active quest", async () => {
+      const questPda = await createOpenQuest();
+      const [contributionPda] = deriveContributionPda(questPda);
+
+      await addToReward(questPda, TOP_UP);
+      await addToReward(questPda, TOP_UP);
+
+      const quest = await program.account.quest.fetch(questPda);
+      expect(quest.rewardAmount.toNumber()).to.equal(REWARD + 2 * TOP_UP);
+      expect(quest.contributors).to.equal(1);
+      const contribution = await program.account.contribution.fetch(contributionPda);
+      expect(contribution.amount.toNumber()).to.equal(2 * TOP_UP);
+    });
+
+    it("creator cannot contribute and backers fund from their own account", async () => {
+      const questPda = await createOpenQuest();
+      const contributeAs = (who: Keypair, from: PublicKey) =>
+        program.methods
+          .addToReward(new BN(TOP_UP))
+          .accounts({
+            quest: questPda,
+            contribution: PublicKey.findProgramAddressSync(
+              [Buffer.from("contribution"), questPda.toBuffer(), who.publicKey.toBuffer()],
+              program.programId
+            )[0],
+            escrow: deriveEscrowPda(questPda)[0],
+            rewardMint: mint,
+            contributor: who.publicKey,
+            contributorTokenAccount: from,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([who])
+          .rpc();
+
+      try {
+        await contributeAs(creator, creatorAta);
+        expect.fail("should have thrown");
+      } catch (err) {
+        const anchorErr = err as AnchorError;
+        expect(anchorErr.error.errorCode.code).to.equal("CreatorCannotContribute");
+      }
+
+      try {
+        await contributeAs(backer, creatorAta);
+        expect.fail("should have thrown");
+      } catch (err) {
+        const anchorErr = err as AnchorError;
+        expect(anchorErr.error.errorCode.code).to.equal("ConstraintRaw");
+      }
+    });
+
+    it("cancelled quest refunds creator and backer pro rata", async () => {
+      const questPda = await createOpenQuest();
+      const [escrowPda] = deriveEscrowPda(questPda);
+      await addToReward(questPda, TOP_UP);
+
+      const creatorBefore = await getTokenBalance(creatorAta);
+      const backerBefore = await getTokenBalance(backerAta);
+
+      await program.methods
+        .cancelQuest()
+        .accounts({
+          quest: questPda,
+          escrow: escrowPda,
+          rewardMint: mint,
+          creatorTokenAccount: creatorAta,
+          unwrapAccount: deriveUnwrapPda(questPda)[0],
+          questDescription: null,
+          creator: creator.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([creator])
+        .rpc();
+
+      expect((await getTokenBalance(creatorAta)) - creatorBefore).to.equal(REWARD);
+      // backer's share waits in escrow until refunded
+      expect(await getTokenBalance(escrowPda)).to.equal(TOP_UP);
+      expect(await isClosed(questPda)).to.be.false;
+
+      await refundContribution(questPda);
+
+      expect((await getTokenBalance(backerAta)) - backerBefore).to.equal(TOP_UP);
+      expect(await isClosed(deriveContributionPda(questPda)[0])).to.be.true;
+      // last refund closes the quest
+      expect(await isClosed(escrowPda)).to.be.true;
+      expect(await isClosed(questPda)).to.be.true;
+    });
+
+    it("approved quest pays the boosted reward and refunds nothing", async () => {
+      const questPda = await createOpenQuest();
+      const [escrowPda] = deriveEscrowPda(questPda);
+      const [claimPda] = deriveClaimPda(questPda, claimer.publicKey);
+      await addToReward(questPda, TOP_UP);
+
+      const boosted = REWARD + TOP_UP;
+      const stakeAmount = (boosted * MIN_STAKE_BPS) / 10000;
+      await program.methods
+        .claimQuest(new BN(stakeAmount), [])
+        .accounts({
+          quest: questPda,
+          claim: claimPda,
+          escrow: escrowPda,
+          rewardMint: mint,
+          claimer: claimer.publicKey,
+          claimerTokenAccount: claimerAta,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([claimer])
+        .rpc();
+
+      try {
+        await addToReward(questPda, TOP_UP);
+        expect.fail("should have thrown");
+      } catch (err) {
+        const anchorErr = err as AnchorError;
+        expect(anchorErr.error.errorCode.code).to.equal("QuestNotActive");
+      }
+
+      await program.methods
+        .submitProof(proofHash("boosted proof"), PROOF_URI, PROOF_SECONDS, FRAME_ROOT)
+        .accounts({ quest: questPda, claim: claimPda, claimer: claimer.publicKey })
+        .signers([claimer])
+        .rpc();
+
+      const claimerBefore = await getTokenBalance(claimerAta);
+      await program.methods
+        .approveCompletion()
+        .accounts({
+          config: configPda,
+          oracleSet: oracleSetPda,
+          quest: questPda,
+          claim: claimPda,
+          escrow: escrowPda,
+          rewardMint: mint,
+          creator: creator.publicKey,
+          creatorTokenAccount: creatorAta,
+          claimer: claimer.publicKey,
+          claimerTokenAccount: claimerAta,
+          treasury: treasuryAta,
+          unwrapAccount: deriveUnwrapPda(questPda)[0],
+          oracle: authority.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      const feeAmount = Math.floor((boosted * feeBps) / 10000);
+      expect((await getTokenBalance(claimerAta)) - claimerBefore).to.equal(
+        boosted - feeAmount + stakeAmount
+      );
+
+      const backerBefore = await getTokenBalance(backerAta);
+      await refundContribution(questPda);
+      expect(await getTokenBalance(backerAta)).to.equal(backerBefore);
+      expect(await isClosed(questPda)).to.be.true;
+    });
+  });
+
+  // =========================================================================
+  // Reputation Stats
+  // =========================================================================
+
+  describe("reputation stats", () => {
+    function fetchStats(wallet: PublicKey) {
+      return program.account.userStats.fetch(deriveStatsPda(wallet)[0]);
+    }
+
+    it("approval credits the claimer and the creator", async () => {
+      const creatorBefore = await fetchStats(creator.publicKey);
+      const claimerBefore = await fetchStats(claimer.publicKey);
+      const { questPda, claimPda } = await createAndSubmitQuest();
+      const stakeAmount = (REWARD * MIN_STAKE_BPS) / 10000;
+      const balanceBefore = await getTokenBalance(claimerAta);
+
+      await program.methods
+        .approveCompletion()
+        .accounts({
+          config: configPda,
+          oracleSet: oracleSetPda,
+          quest: questPda,
+          claim: claimPda,
+          escrow: deriveEscrowPda(questPda)[0],
+          rewardMint: mint,
+          creator: creator.publicKey,
+          creatorTokenAccount: creatorAta,
+          claimer: claimer.publicKey,
+          claimerTokenAccount: claimerAta,
+          treasury: treasuryAta,
+          unwrapAccount: deriveUnwrapPda(questPda)[0],
+          oracle: authority.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      const earned = (await getTokenBalance(claimerAta)) - balanceBefore - stakeAmount;
+      const creatorAfter = await fetchStats(creator.publicKey);
+      const claimerAfter = await fetchStats(claimer.publicKey);
+
+      expect(creatorAfter.questsPosted).to.equal(creatorBefore.questsPosted + 1);
+      expect(
+        creatorAfter.totalPaidOut.sub(creatorBefore.totalPaidOut).toNumber()
+      ).to.equal(REWARD);
+      expect(claimerAfter.questsCompleted).to.equal(
+        claimerBefore.questsCompleted + 1
+      );
+      expect(
+        claimerAfter.totalEarned.sub(claimerBefore.totalEarned).toNumber()
+      ).to.equal(earned);
+    });
+
+    it("final rejection counts against the claimer", async () => {
+      const before = await fetchStats(claimer.publicKey);
+      const { questPda, claimPda } = await createAndSubmitQuest();
+
+      await rejectAsOracle(questPda, claimPda);
+      await sleep((DISPUTE_WINDOW + 2) * 1000);
+      await finalizeRejection(questPda, claimPda);
+
+      const after = await fetchStats(claimer.publicKey);
+      expect(after.claimsRejected).to.equal(before.claimsRejected + 1);
+      expect(after.questsCompleted).to.equal(before.questsCompleted);
+    });
+  });
+
+  // =========================================================================
+  // Rate Limits
+  // =========================================================================
+
+  describe("rate limits", () => {
+    const abuser = Keypair.generate();
+    let abuserAta: PublicKey;
+
+    async function createSmallQuest(label: string): Promise<PublicKey> {
+      return (await createTestQuest({ reward: ONE_TOKEN, label })).questPda;
+    }
+
+    function claimAsAbuser(questPda: PublicKey) {
+      return program.methods
+        .claimQuest(new BN((ONE_TOKEN * MIN_STAKE_BPS) / 10000), [])
+        .accounts({
+          quest: questPda,
+          claim: deriveClaimPda(questPda, abuser.publicKey)[0],
+          escrow: deriveEscrowPda(questPda)[0],
+          rewardMint: mint,
+          claimer: abuser.publicKey,
+          claimerTokenAccount: abuserAta,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([abuser])
+        .rpc();
+    }
+
+    function abandonAsAbuser(questPda: PublicKey) {
+      return program.methods
+        .abandonClaim()
+        .accounts({
+          quest: questPda,
+          claim: deriveClaimPda(questPda, abuser.publicKey)[0],
+          escrow: deriveEscrowPda(questPda)[0],
+          rewardMint: mint,
+          creator: creator.publicKey,
+          creatorTokenAccount: creatorAta,
+          treasury: treasuryAta,
+          unwrapAccount: deriveUnwrapPda(questPda)[0],
+          claimer: abuser.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([abuser])
+        .rpc();
+    }
+
+    before(async () => {
+      await airdrop(abuser.publicKey);
+      abuserAta = await createAccount(
+        connection,
+        (authority as any).payer,
+        mint,
+        abuser.publicKey
+      );
+      await mintTo(
+        connection,
+        (authority as any).payer,
+        mint,
+        abuserAta,
+        authority.publicKey,
+        100 * ONE_TOKEN
+      );
+    });
+
+    it("tracks quests and active claims on the profile", async () => {
+      const before = await program.account.userProfile.fetch(
+        deriveProfilePda(creator.publicKey)[0]
+      );
+      const questPda = await createSmallQuest("profile counters");
+
+      const creatorProfile = await program.account.userProfile.fetch(
+        deriveProfilePda(creator.publicKey)[0]
+      );
+      expect(creatorProfile.wallet.toBase58()).to.equal(
+        creator.publicKey.toBase58()
+      );
+      expect(creatorProfile.questsInWindow).to.equal(before.questsInWindow + 1);
+
+      await claimAsAbuser(questPda);
+      let profile = await program.account.userProfile.fetch(
+        deriveProfilePda(abuser.publicKey)[0]
+      );
+      expect(profile.activeClaims).to.equal(1);
+      expect(profile.recentFailures).to.have.length(0);
+
+      await abandonAsAbuser(questPda);
+      profile = await program.account.userProfile.fetch(
+        deriveProfilePda(abuser.publicKey)[0]
+      );
+      expect(profile.activeClaims).to.equal(0);
+      expect(profile.recentFailures).to.have.length(1);
+
+      const stats = await program.account.userStats.fetch(
+        deriveStatsPda(abuser.publicKey)[0]
+      );
+      expect(stats.claimsAbandoned).to.equal(1);
+    });
+
+    it("blocks claims after too many recent failures", async () => {
+      // one failure from the previous test, two more reach the limit
+      for (let i = 0; i < rateLimits.maxRecentFailures - 1; i++) {
+        const questPda = await createSmallQuest(`flaky ${i}`);
+        await claimAsAbuser(questPda);
+        await abandonAsAbuser(questPda);
+      }
+
+      const questPda = await createSmallQuest("one too many");
+      try {
+        await claimAsAbuser(questPda);
+        expect.fail("should have thrown");
+      } catch (err) {
+        const anchorErr = err as AnchorError;
+        expect(anchorErr.error.errorCode.code).to.equal("TooManyRecentFailures");
+      }
+    });
+  });
+
+  // =========================================================================
+  // Strikes & Bans
+  // =========================================================================
+
+  describe("strikes and bans", () => {
+    const offender = Keypair.generate();
+    let offenderAta: PublicKey;
+    let questPda: PublicKey;
+
+    function flag(wallet: PublicKey) {
+      return program.methods
+        .flagAccount(wallet)
+        .accounts({
+          config: configPda,
+          profile: deriveProfilePda(wallet)[0],
+          authority: authority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+    }
+
+    function claimAsOffender() {
+      return program.methods
+        .claimQuest(new BN((REWARD * MIN_STAKE_BPS) / 10000), [])
+        .accounts({
+          quest: questPda,
+          claim: deriveClaimPda(questPda, offender.publicKey)[0],
+          escrow: deriveEscrowPda(questPda)[0],
+          rewardMint: mint,
+          claimer: offender.publicKey,
+          claimerTokenAccount: offenderAta,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([offender])
+        .rpc();
+    }
+
+    before(async () => {
+      await airdrop(offender.publicKey);
+      offenderAta = await createAccount(
+        connection,
+        (authority as any).payer,
+        mint,
+        offender.publicKey
+      );
+      await mintTo(
+        connection,
+        (authority as any).payer,
+        mint,
+        offenderAta,
+        authority.publicKey,
+        100 * ONE_TOKEN
+      );
+
+      ({ questPda } = await createTestQuest({ label: "strike test" }));
+    });
+
+    it("non-authority cannot flag an account", async () => {
+      try {
+        await program.methods
+          .flagAccount(offender.publicKey)
+          .accounts({
+            config: configPda,
+            profile: deriveProfilePda(offender.publicKey)[0],
+            authority: randomUser.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([randomUser])
+          .rpc();
+        expect.fail("should have thrown");
+      } catch (err) {
+        const anchorErr = err as AnchorError;
+        expect(anchorErr.error.errorCode.code).to.equal("NotAuthority");
+      }
+    });
+
+    it("third strike bans the wallet from claiming", async () => {
+      for (let i = 0; i < 3; i++) {
+        await flag(offender.publicKey);
+      }
+
+      const profile = await program.account.userProfile.fetch(
+        deriveProfilePda(offender.publicKey)[0]
+      );
+      expect(profile.strikes).to.equal(3);
+      expect(profile.banned).to.be.true;
+
+      try {
+        await claimAsOffender();
+        expect.fail("should have thrown");
+      } catch (err) {
+        const anchorErr = err as AnchorError;
+        expect(anchorErr.error.errorCode.code).to.equal("AccountBanned");
+      }
+    });
+
+    it("authority can lift a ban on appeal", async () => {
+      await program.methods
+        .unbanAccount(offender.publicKey)
+        .accounts({
+          config: configPda,
+          profile: deriveProfilePda(offender.publicKey)[0],
+          authority: authority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      const profile = await program.account.userProfile.fetch(
+        deriveProfilePda(offender.publicKey)[0]
+      );
+      expect(profile.strikes).to.equal(0);
+      expect(profile.banned).to.be.false;
+
+      await claimAsOffender();
+      const claim = await program.account.claim.fetch(
+        deriveClaimPda(questPda, offender.publicKey)[0]
+      );
+      expect(claim.status).to.deep.equal({ active: {} });
+    });
+  });
+
+  // =========================================================================
+  // Chain Quests
+  // =========================================================================
+
+  describe("chain quests", () => {
+    const steps = [
+      { descriptionHash: descHash("step one"), shareBps: 4000 },
+      { descriptionHash: descHash("step two"), shareBps: 6000 },
+    ];
+
+    async function createChainQuest(
+      timeLimit: BN | null = null
+    ): Promise<{ questPda: PublicKey; claimPda: PublicKey }> {
+      const { questPda } = await createTestQuest({
+        timeLimit,
+        steps,
+        label: "chain quest",
+      });
+
+      const [claimPda] = deriveClaimPda(questPda, claimer.publicKey);
+      await program.methods
+        .claimQuest(new BN((REWARD * MIN_STAKE_BPS) / 10000), [])
+        .accounts({
+          quest: questPda,
+          claim: claimPda,
+          escrow: deriveEscrowPda(questPda)[0],
+          rewardMint: mint,
+          claimer: claimer.publicKey,
+          claimerTokenAccount: claimerAta,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([claimer])
+        .rpc();
+
+      return { questPda, claimPda };
+    }
+
+    async function submitAndApprove(questPda: PublicKey, claimPda: PublicKey) {
+      await program.methods
+        .submitProof(proofHash("chain step proof"), PROOF_URI, PROOF_SECONDS, FRAME_ROOT)
+        .accounts({
+          quest: questPda,
+          claim: claimPda,
+          claimer: claimer.publicKey,
+        })
+        .signers([claimer])
+        .rpc();
+
+      await program.methods
+        .approveCompletion()
+        .accounts({
+          config: configPda,
+          oracleSet: oracleSetPda,
+          quest: questPda,
+          claim: claimPda,
+          escrow: deriveEscrowPda(questPda)[0],
+          rewardMint: mint,
+          creator: creator.publicKey,
+          creatorTokenAccount: creatorAta,
+          claimer: claimer.publicKey,
+          claimerTokenAccount: claimerAta,
+          treasury: treasuryAta,
+          unwrapAccount: deriveUnwrapPda(questPda)[0],
+          oracle: authority.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+    }
+
+    it("rejects steps whose shares do not add up", async () => {
+      const [questPda] = deriveQuestPda(questCount);
+      try {
+        await program.methods
+          .createQuest(
+            new BN(REWARD),
+            { open: {} },
+            null,
+            1,
+            null,
+            PROOF_HOURS,
+            REVIEW_HOURS,
+            descHash("bad chain"),
+            false,
+            [steps[0], { ...steps[1], shareBps: 5000 }],
+            null
+          )
+          .accounts({
+            config: configPda,
+            quest: questPda,
+            questDescription: null,
+            escrow: deriveEscrowPda(questPda)[0],
+            rewardMint: mint,
+            creator: creator.publicKey,
+            creatorTokenAccount: creatorAta,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([creator])
+          .rpc();
+        expect.fail("should have thrown");
+      } catch (err) {
+        const anchorErr = err as AnchorError;
+        expect(anchorErr.error.errorCode.code).to.equal("InvalidQuestSteps");
+      }
+    });
+
+    it("each approval releases its step's share", async () => {
+      const { feeBasisPoints } = await program.account.questConfig.fetch(configPda);
+      const afterFee = (amount: number) =>
+        amount - Math.floor((amount * feeBasisPoints) / 10000);
+      const stakeAmount = (REWARD * MIN_STAKE_BPS) / 10000;
+      const firstShare = (REWARD * steps[0].shareBps) / 10000;
+
+      const { questPda, claimPda } = await createChainQuest();
+      const claimerBefore = await getTokenBalance(claimerAta);
+      const [profilePda] = deriveProfilePda(claimer.publicKey);
+      const activeBefore = (await program.account.userProfile.fetch(profilePda))
+        .activeClaims;
+
+      await submitAndApprove(questPda, claimPda);
+
+      expect((await getTokenBalance(claimerAta)) - claimerBefore).to.equal(
+        afterFee(firstShare)
+      );
+      const claim = await program.account.claim.fetch(claimPda);
+      expect(claim.status).to.deep.equal({ active: {} });
+      expect(claim.currentStep).to.equal(1);
+      expect(claim.proofHash).to.be.null;
+      // back to waiting on proof for the next step
+      expect(
+        (await program.account.userProfile.fetch(profilePda)).activeClaims
+      ).to.equal(activeBefore);
+      const quest = await program.account.quest.fetch(questPda);
+      expect(quest.stepsPaid.toNumber()).to.equal(firstShare);
+
+      await submitAndApprove(questPda, claimPda);
+
+      // last step pays the remainder plus the stake and settles the quest
+      expect((await getTokenBalance(claimerAta)) - claimerBefore).to.equal(
+        afterFee(firstShare) + afterFee(REWARD - firstShare) + stakeAmount
+      );
+      expect(await isClosed(questPda)).to.be.true;
+      expect(
+        (await program.account.userProfile.fetch(profilePda)).activeClaims
+      ).to.equal(activeBefore - 1);
+      expect(await isClosed(claimPda)).to.be.true;
+    });
+
+    it("a step approved with no window left ends the chain without forfeiture", async () => {
+      const { feeBasisPoints } = await program.account.questConfig.fetch(configPda);
+      const now = Math.floor(Date.now() / 1000);
+      const stakeAmount = (REWARD * MIN_STAKE_BPS) / 10000;
+      const firstShare = (REWARD * steps[0].shareBps) / 10000;
+
+      // proof deadlines are clamped to the time limit, so no second step fits
+      const { questPda, claimPda } = await createChainQuest(new BN(now + 6));
+      const claimerBefore = await getTokenBalance(claimerAta);
+      const creatorBefore = await getTokenBalance(creatorAta);
+
+      await submitAndApprove(questPda, claimPda);
+
+      // the claimer keeps the first step's share and gets the stake back
+      expect((await getTokenBalance(claimerAta)) - claimerBefore).to.equal(
+        firstShare - Math.floor((firstShare * feeBasisPoints) / 10000) + stakeAmount
+      );
+      expect((await getTokenBalance(creatorAta)) - creatorBefore).to.equal(
+        REWARD - firstShare
+      );
+      expect(await isClosed(claimPda)).to.be.true;
+      expect(await isClosed(questPda)).to.be.true;
+    });
+  });
+
+  // =========================================================================
+  // Guild Claims
+  // =========================================================================
+
+  describe("guild claims", () => {
+    const partner = Keypair.generate();
+    let partnerAta: PublicKey;
+    const minStake = (REWARD * MIN_STAKE_BPS) / 10000;
+
+    async function createGuildQuest(): Promise<PublicKey> {
+      return (await createTestQuest({ label: "group dare" })).questPda;
+    }
+
+    function claimAsGuild(
+      questPda: PublicKey,
+      leader: Keypair,
+      leaderAta: PublicKey,
+      members: { wallet: PublicKey; weightBps: number }[]
+    ) {
+      return program.methods
+        .claimQuest(new BN((minStake * members[0].weightBps) / 10000), members)
+        .accounts({
+          quest: questPda,
+          claim: deriveClaimPda(questPda, leader.publicKey)[0],
+          escrow: deriveEscrowPda(questPda)[0],
+          rewardMint: mint,
+          claimer: leader.publicKey,
+          claimerTokenAccount: leaderAta,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([leader])
+        .rpc();
+    }
+
+    function joinGuild(
+      questPda: PublicKey,
+      claimPda: PublicKey,
+      member: Keypair,
+      memberAta: PublicKey,
+      weightBps: number
+    ) {
+      return program.methods
+        .joinGuild(new BN((minStake * weightBps) / 10000))
+        .accounts({
+          quest: questPda,
+          claim: claimPda,
+          escrow: deriveEscrowPda(questPda)[0],
+          rewardMint: mint,
+          member: member.publicKey,
+          memberTokenAccount: memberAta,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([member])
+        .rpc();
+    }
+
+    before(async () => {
+      await airdrop(partner.publicKey);
+      partnerAta = await createAccount(
+        connection,
+        (authority as any).payer,
+        mint,
+        partner.publicKey
+      );
+      await mintTo(
+        connection,
+        (authority as any).payer,
+        mint,
+        partnerAta,
+        authority.publicKey,
+        100 * ONE_TOKEN
+      );
+    });
+
+    it("rejects weights that do not add up", async () => {
+      const questPda = await createGuildQuest();
+      try {
+        await claimAsGuild(questPda, claimer, claimerAta, [
+          { wallet: claimer.publicKey, weightBps: 5000 },
+          { wallet: partner.publicKey, weightBps: 4000 },
+        ]);
+        expect.fail("should have thrown");
+      } catch (err) {
+        const anchorErr = err as AnchorError;
+        expect(anchorErr.error.errorCode.code).to.equal("InvalidGuild");
+      }
+    });
+
+    it("quest creator cannot join a guild on their own quest", async () => {
+      const questPda = await createGuildQuest();
+      try {
+        await claimAsGuild(questPda, claimer, claimerAta, [
+          { wallet: claimer.publicKey, weightBps: 5000 },
+          { wallet: creator.publicKey, weightBps: 5000 },
+        ]);
+        expect.fail("should have thrown");
+      } catch (err) {
+        const anchorErr = err as AnchorError;
+        expect(anchorErr.error.errorCode.code).to.equal("CannotClaimOwnQuest");
+      }
+    });
+
+    it("a banned wallet cannot join a guild", async () => {
+      const outcast = Keypair.generate();
+      const outcastAta = await createAccount(
+        connection,
+        (authority as any).payer,
+        mint,
+        outcast.publicKey
+      );
+      for (let i = 0; i < 3; i++) {
+        await program.methods
+          .flagAccount(outcast.publicKey)
+          .accounts({
+            config: configPda,
+            profile: deriveProfilePda(outcast.publicKey)[0],
+            authority: authority.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+      }
+
+      const questPda = await createGuildQuest();
+      const [claimPda] = deriveClaimPda(questPda, partner.publicKey);
+      await claimAsGuild(questPda, partner, partnerAta, [
+        { wallet: partner.publicKey, weightBps: 5000 },
+        { wallet: outcast.publicKey, weightBps: 5000 },
+      ]);
+
+      try {
+        await joinGuild(questPda, claimPda, outcast, outcastAta, 5000);
+        expect.fail("should have thrown");
+      } catch (err) {
+        const anchorErr = err as AnchorError;
+        expect(anchorErr.error.errorCode.code).to.equal("AccountBanned");
+      }
+    });
+
+    it("members stake, leader proves, approval splits the reward", async () => {
+      const { feeBasisPoints } = await program.account.questConfig.fetch(configPda);
+      const rewardAfterFee = REWARD - Math.floor((REWARD * feeBasisPoints) / 10000);
+      const members = [
+        { wallet: claimer.publicKey, weightBps: 6000 },
+        { wallet: partner.publicKey, weightBps: 4000 },
+      ];
+      const questPda = await createGuildQuest();
+      const [claimPda] = deriveClaimPda(questPda, claimer.publicKey);
+
+      await claimAsGuild(questPda, claimer, claimerAta, members);
+      let claim = await program.account.claim.fetch(claimPda);
+      expect(claim.status).to.deep.equal({ forming: {} });
+
+      const claimerBefore = await getTokenBalance(claimerAta);
+      const partnerBefore = await getTokenBalance(partnerAta);
+      const [leaderStatsPda] = deriveStatsPda(claimer.publicKey);
+      const earnedBefore = (await program.account.userStats.fetch(leaderStatsPda))
+        .totalEarned.toNumber();
+
+      await joinGuild(questPda, claimPda, partner, partnerAta, 4000);
+      claim = await program.account.claim.fetch(claimPda);
+      expect(claim.status).to.deep.equal({ active: {} });
+      expect(claim.stakeAmount.toNumber()).to.equal(minStake);
+
+      await program.methods
+        .submitProof(proofHash("group proof"), PROOF_URI, PROOF_SECONDS, FRAME_ROOT)
+        .accounts({
+          quest: questPda,
+          claim: claimPda,
+          claimer: claimer.publicKey,
+        })
+        .signers([claimer])
+        .rpc();
+
+      await program.methods
+        .approveCompletion()
+        .accounts({
+          config: configPda,
+          oracleSet: oracleSetPda,
+          quest: questPda,
+          claim: claimPda,
+          escrow: deriveEscrowPda(questPda)[0],
+          rewardMint: mint,
+          creator: creator.publicKey,
+          creatorTokenAccount: creatorAta,
+          claimer: claimer.publicKey,
+          claimerTokenAccount: claimerAta,
+          treasury: treasuryAta,
+          unwrapAccount: deriveUnwrapPda(questPda)[0],
+          oracle: authority.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts([
+          { pubkey: claimerAta, isWritable: true, isSigner: false },
+          { pubkey: partnerAta, isWritable: true, isSigner: false },
+        ])
+        .rpc();
+
+      const leaderShare = Math.floor((rewardAfterFee * 6000) / 10000);
+      expect((await getTokenBalance(claimerAta)) - claimerBefore).to.equal(
+        leaderShare + (minStake * 6000) / 10000
+      );
+      // partner staked after the snapshot, so they net their reward share
+      expect((await getTokenBalance(partnerAta)) - partnerBefore).to.equal(
+        rewardAfterFee - leaderShare
+      );
+      // the leader's stats only count the leader's own share
+      expect(
+        (await program.account.userStats.fetch(leaderStatsPda)).totalEarned.toNumber() -
+          earnedBefore
+      ).to.equal(leaderShare);
+      expect(await isClosed(claimPda)).to.be.true;
+    });
+
+    it("abandoning forfeits every member's stake", async () => {
+      const questPda = await createGuildQuest();
+      const [claimPda] = deriveClaimPda(questPda, partner.publicKey);
+
+      await claimAsGuild(questPda, partner, partnerAta, [
+        { wallet: partner.publicKey, weightBps: 5000 },
+        { wallet: claimer.publicKey, weightBps: 5000 },
+      ]);
+      await joinGuild(questPda, claimPda, claimer, claimerAta, 5000);
+
+      const creatorBefore = await getTokenBalance(creatorAta);
+
+      await program.methods
+        .abandonClaim()
+        .accounts({
+          quest: questPda,
+          claim: claimPda,
+          escrow: deriveEscrowPda(questPda)[0],
+          rewardMint: mint,
+          creator: creator.publicKey,
+          creatorTokenAccount: creatorAta,
+          treasury: treasuryAta,
+          unwrapAccount: deriveUnwrapPda(questPda)[0],
+          claimer: partner.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([partner])
+        .rpc();
+
+      expect((await getTokenBalance(creatorAta)) - creatorBefore).to.equal(
+        creatorForfeitShare(minStake)
+      );
+    });
+  });
+
+  // =========================================================================
+  // Partial Credit
+  // =========================================================================
+
+  describe("partial credit", () => {
+    function settlePartial(questPda: PublicKey, claimPda: PublicKey, bps: number) {
+      return program.methods
+        .settlePartial(bps)
+        .accounts({
+          config: configPda,
+          oracleSet: oracleSetPda,
+          quest: questPda,
+          claim: claimPda,
+          escrow: deriveEscrowPda(questPda)[0],
+          rewardMint: mint,
+          creator: creator.publicKey,
+          creatorTokenAccount: creatorAta,
+          claimer: claimer.publicKey,
+          claimerTokenAccount: claimerAta,
+          treasury: treasuryAta,
+          unwrapAccount: deriveUnwrapPda(questPda)[0],
+          oracle: authority.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+    }
+
+    it("pays a share of the reward and refunds the rest", async () => {
+      const { feeBasisPoints } = await program.account.questConfig.fetch(configPda);
+      const stakeAmount = (REWARD * MIN_STAKE_BPS) / 10000;
+      const awarded = (REWARD * 7500) / 10000;
+      const fee = Math.floor((awarded * feeBasisPoints) / 10000);
+
+      const { questPda, claimPda } = await createAndSubmitQuest();
+      const claimerBefore = await getTokenBalance(claimerAta);
+      const creatorBefore = await getTokenBalance(creatorAta);
+
+      await settlePartial(questPda, claimPda, 7500);
+
+      expect((await getTokenBalance(claimerAta)) - claimerBefore).to.equal(
+        awarded - fee + stakeAmount
+      );
+      expect((await getTokenBalance(creatorAta)) - creatorBefore).to.equal(
+        REWARD - awarded
+      );
+      expect(await isClosed(questPda)).to.be.true;
+    });
+
+    it("rejects a full or empty award", async () => {
+      const { questPda, claimPda } = await createAndSubmitQuest();
+      for (const bps of [0, 10000]) {
+        try {
+          await settlePartial(questPda, claimPda, bps);
+          expect.fail("should have thrown");
+        } catch (err) {
+          const anchorErr = err as AnchorError;
+          expect(anchorErr.error.errorCode.code).to.equal("InvalidAwardBps");
+        }
+      }
+    });
+  });
+
+  // =========================================================================
+  // Proof Revisions
+  // =========================================================================
+
+  describe("proof revisions", () => {
+    // no reviewer keypair means the provider wallet signs as the oracle
+    function requestRevision(questPda: PublicKey, claimPda: PublicKey, reviewer?: Keypair) {
+      return program.methods
+        .requestRevision()
+        .accounts({
+          config: configPda,
+          oracleSet: oracleSetPda,
+          quest: questPda,
+          claim: claimPda,
+          reviewer: reviewer ? reviewer.publicKey : authority.publicKey,
+        })
+        .signers(reviewer ? [reviewer] : [])
+        .rpc();
+    }
+
+    function resubmit(questPda: PublicKey, claimPda: PublicKey, data: string) {
+      return program.methods
+        .submitProof(proofHash(data), PROOF_URI, PROOF_SECONDS, FRAME_ROOT)
+        .accounts({
+          quest: questPda,
+          claim: claimPda,
+          claimer: claimer.publicKey,
+        })
+        .signers([claimer])
+        .rpc();
+    }
+
+    it("oracle sends the proof back and the claimer resubmits", async () => {
+      const { questPda, claimPda } = await createAndSubmitQuest();
+      const [profilePda] = deriveProfilePda(claimer.publicKey);
+      const activeBefore = (await program.account.userProfile.fetch(profilePda))
+        .activeClaims;
+
+      await requestRevision(questPda, claimPda);
+
+      // the claim is waiting on proof again, so it counts as active
+      expect(
+        (await program.account.userProfile.fetch(profilePda)).activeClaims
+      ).to.equal(activeBefore + 1);
+
+      let claim = await program.account.claim.fetch(claimPda);
+      expect(claim.status).to.deep.equal({ needsRevision: {} });
+      expect(claim.proofHash).to.be.null;
+      expect(claim.reviewDeadline).to.be.null;
+      expect(claim.revisions).to.equal(1);
+
+      await resubmit(questPda, claimPda, "sharper video");
+
+      claim = await program.account.claim.fetch(claimPda);
+      expect(claim.status).to.deep.equal({ submitted: {} });
+      expect(claim.proofHash).to.deep.equal(proofHash("sharper video"));
+      expect(
+        (await program.account.userProfile.fetch(profilePda)).activeClaims
+      ).to.equal(activeBefore);
+    });
+
+    it("creator can request a revision during review", async () => {
+      const { questPda, claimPda } = await createAndSubmitQuest();
+
+      await requestRevision(questPda, claimPda, creator);
+
+      const claim = await program.account.claim.fetch(claimPda);
+      expect(claim.status).to.deep.equal({ needsRevision: {} });
+    });
+
+    it("caps revisions per claim", async () => {
+      const { questPda, claimPda } = await createAndSubmitQuest();
+
+      for (let i = 0; i < 2; i++) {
+        await requestRevision(questPda, claimPda, creator);
+        await resubmit(questPda, claimPda, `retry ${i}`);
+      }
+
+      try {
+        await requestRevision(questPda, claimPda, creator);
+        expect.fail("should have thrown");
+      } catch (err) {
+        const anchorErr = err as AnchorError;
+        expect(anchorErr.error.errorCode.code).to.equal("RevisionLimitReached");
+      }
+    });
+
+    it("refuses a revision once the time limit leaves no proof window", async () => {
+      const now = await connection.getBlockTime(await connection.getSlot());
+      const { questPda, escrowPda } = await createTestQuest({
+        timeLimit: new BN(now + 3),
+        label: "revision past the time limit",
+      });
+      const [claimPda] = deriveClaimPda(questPda, claimer.publicKey);
+
+      await program.methods
+        .claimQuest(new BN((REWARD * MIN_STAKE_BPS) / 10000), [])
+        .accounts({
+          quest: questPda,
+          claim: claimPda,
+          escrow: escrowPda,
+          rewardMint: mint,
+          claimer: claimer.publicKey,
+          claimerTokenAccount: claimerAta,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([claimer])
+        .rpc();
+      await resubmit(questPda, claimPda, "just in time");
+
+      await sleep(5000);
+
+      // the creator can still review, but a sent-back proof could never be redone
+      try {
+        await requestRevision(questPda, claimPda, creator);
+        expect.fail("should have thrown");
+      } catch (err) {
+        const anchorErr = err as AnchorError;
+        expect(anchorErr.error.errorCode.code).to.equal("NoProofWindowLeft");
+      }
+      const claim = await program.account.claim.fetch(claimPda);
+      expect(claim.status).to.deep.equal({ submitted: {} });
+    });
+
+    it("rejects a reviewer who is neither creator nor oracle", async () => {
+      const { questPda, claimPda } = await createAndSubmitQuest();
+
+      try {
+        await requestRevision(questPda, claimPda, randomUser);
+        expect.fail("should have thrown");
+      } catch (err) {
+        const anchorErr = err as AnchorError;
+        expect(anchorErr.error.errorCode.code).to.equal("NotOracle");
+      }
+    });
+  });
+
   // =========================================================================
   // Edge Cases
   // =========================================================================
@@ -1330,11 +5216,17 @@ describe("quest", () => {
             null,
             1,
             new BN(1000), // unix timestamp in 1970 — long past
-            descHash("expired before creation")
+            PROOF_HOURS,
+            REVIEW_HOURS,
+            descHash("expired before creation"),
+            false,
+            [],
+            null
           )
           .accounts({
             config: configPda,
             quest: questPda,
+            questDescription: null,
             escrow: escrowPda,
             rewardMint: mint,
             creator: creator.publicKey,
@@ -1363,11 +5255,17 @@ describe("quest", () => {
           null,
           1,
           null,
-          descHash("will cancel")
+          PROOF_HOURS,
+          REVIEW_HOURS,
+          descHash("will cancel"),
+          false,
+          [],
+          null
         )
         .accounts({
           config: configPda,
           quest: questPda,
+          questDescription: null,
           escrow: escrowPda,
           rewardMint: mint,
           creator: creator.publicKey,
@@ -1384,9 +5282,13 @@ describe("quest", () => {
         .accounts({
           quest: questPda,
           escrow: escrowPda,
+          rewardMint: mint,
           creator: creator.publicKey,
           creatorTokenAccount: creatorAta,
+          unwrapAccount: deriveUnwrapPda(questPda)[0],
+          questDescription: null,
           tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
         })
         .signers([creator])
         .rpc();
@@ -1395,11 +5297,12 @@ describe("quest", () => {
 
       try {
         await program.methods
-          .claimQuest(new BN((REWARD * MIN_STAKE_BPS) / 10000))
+          .claimQuest(new BN((REWARD * MIN_STAKE_BPS) / 10000), [])
           .accounts({
             quest: questPda,
             claim: claimPda,
             escrow: escrowPda,
+            rewardMint: mint,
             claimer: claimer.publicKey,
             claimerTokenAccount: claimerAta,
             tokenProgram: TOKEN_PROGRAM_ID,
@@ -1409,8 +5312,9 @@ describe("quest", () => {
           .rpc();
         expect.fail("should have thrown");
       } catch (err) {
+        // cancelling closes the quest account outright
         const anchorErr = err as AnchorError;
-        expect(anchorErr.error.errorCode.code).to.equal("QuestNotActive");
+        expect(anchorErr.error.errorCode.code).to.equal("AccountNotInitialized");
       }
     });
 
@@ -1427,11 +5331,57 @@ describe("quest", () => {
             null,
             0, // invalid
             null,
-            descHash("zero claimers")
+            PROOF_HOURS,
+            REVIEW_HOURS,
+            descHash("zero claimers"),
+            false,
+            [],
+            null
+          )
+          .accounts({
+            config: configPda,
+            quest: questPda,
+            questDescription: null,
+            escrow: escrowPda,
+            rewardMint: mint,
+            creator: creator.publicKey,
+            creatorTokenAccount: creatorAta,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([creator])
+          .rpc();
+        expect.fail("should have thrown");
+      } catch (err) {
+        const anchorErr = err as AnchorError;
+        expect(anchorErr.error.errorCode.code).to.equal("InvalidMaxClaimers");
+      }
+    });
+
+    it("non-pooled quest with several claimers rejected", async () => {
+      const id = questCount;
+      const [questPda] = deriveQuestPda(id);
+      const [escrowPda] = deriveEscrowPda(questPda);
+
+      try {
+        await program.methods
+          .createQuest(
+            new BN(REWARD),
+            { open: {} },
+            null,
+            3, // one reward cannot pay three winners
+            null,
+            PROOF_HOURS,
+            REVIEW_HOURS,
+            descHash("several claimers, one reward"),
+            false,
+            [],
+            null
           )
           .accounts({
             config: configPda,
             quest: questPda,
+            questDescription: null,
             escrow: escrowPda,
             rewardMint: mint,
             creator: creator.publicKey,
@@ -1453,7 +5403,7 @@ describe("quest", () => {
 
       try {
         await program.methods
-          .submitProof(proofHash("second-attempt"))
+          .submitProof(proofHash("second-attempt"), PROOF_URI, PROOF_SECONDS, FRAME_ROOT)
           .accounts({
             quest: questPda,
             claim: claimPda,
@@ -1478,9 +5428,14 @@ describe("quest", () => {
             quest: questPda,
             claim: claimPda,
             escrow: escrowPda,
+            rewardMint: mint,
+            creator: creator.publicKey,
             creatorTokenAccount: creatorAta,
+            treasury: treasuryAta,
+            unwrapAccount: deriveUnwrapPda(questPda)[0],
             claimer: claimer.publicKey,
             tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
           })
           .signers([claimer])
           .rpc();
@@ -1491,9 +5446,77 @@ describe("quest", () => {
       }
     });
 
-    it("quest count increments correctly across multiple creates", async () => {
-      const config = await program.account.questConfig.fetch(configPda);
-      expect(config.questCount.toNumber()).to.equal(questCount);
+    it("creator nonce increments correctly across multiple creates", async () => {
+      const counter = await program.account.questCounter.fetch(
+        deriveQuestCounterPda(creator.publicKey)[0]
+      );
+      expect(counter.nextNonce.toNumber()).to.equal(questCount);
+    });
+
+    it("quest nonces are per creator and the global count is optional", async () => {
+      const otherCreator = Keypair.generate();
+      await airdrop(otherCreator.publicKey);
+      const otherAta = await createAccount(
+        connection,
+        (authority as any).payer,
+        mint,
+        otherCreator.publicKey
+      );
+      await mintTo(
+        connection,
+        (authority as any).payer,
+        mint,
+        otherAta,
+        authority.publicKey,
+        10 * REWARD
+      );
+
+      const globalBefore = (
+        await program.account.globalStats.fetch(globalStatsPda)
+      ).questCount.toNumber();
+
+      // a new creator starts at nonce 0 regardless of other creators' quests
+      for (const [id, globalStats] of [globalStatsPda, null].entries()) {
+        const [questPda] = deriveQuestPda(id, otherCreator.publicKey);
+
+        await program.methods
+          .createQuest(
+            new BN(REWARD),
+            { open: {} },
+            null,
+            1,
+            null,
+            PROOF_HOURS,
+            REVIEW_HOURS,
+            descHash(`other creator quest ${id}`),
+            false,
+            [],
+            null
+          )
+          .accounts({
+            config: configPda,
+            quest: questPda,
+            questDescription: null,
+            globalStats,
+            escrow: deriveEscrowPda(questPda)[0],
+            rewardMint: mint,
+            creator: otherCreator.publicKey,
+            creatorTokenAccount: otherAta,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([otherCreator])
+          .rpc();
+
+        const quest = await program.account.quest.fetch(questPda);
+        expect(quest.id.toNumber()).to.equal(id);
+      }
+
+      // only the create that passed the stats account was counted
+      const globalAfter = (
+        await program.account.globalStats.fetch(globalStatsPda)
+      ).questCount.toNumber();
+      expect(globalAfter - globalBefore).to.equal(1);
     });
   });
 });